@@ -0,0 +1,468 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    path::PathBuf,
+};
+
+use bevy::{ecs::system::SystemParam, input::InputSystem, prelude::*};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::storage::{config_dir, load_ron, save_ron};
+
+// every system reads what the player meant rather than which key they pressed, so the keys can be
+// rebound from the controls screen
+pub fn actions_plugin(app: &mut App) {
+    app.insert_resource(load_bindings())
+        .init_resource::<ActionState>()
+        .add_systems(PreUpdate, update_actions.after(InputSystem))
+        .add_systems(
+            Update,
+            save_bindings.run_if(resource_changed::<Bindings>.and(not(resource_added::<Bindings>))),
+        );
+}
+
+// bump when an action changes meaning, older files are still read with defaults for anything missing
+const BINDINGS_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum Action {
+    Select, // drags the selection rectangle out from the pointer
+    Confirm,
+    Cancel,
+    Pause,
+    Hint, // bindable but nothing answers it yet, hints need a cost before they go in
+    Up,
+    Down,
+    Left,
+    Right,
+    Extend, // held while moving the grid cursor to grow a selection
+}
+
+impl Action {
+    // in the order they're listed on the controls screen
+    pub const ALL: [Action; 10] = [
+        Action::Select,
+        Action::Confirm,
+        Action::Cancel,
+        Action::Pause,
+        Action::Hint,
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Extend,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Select => "Select",
+            Action::Confirm => "Confirm",
+            Action::Cancel => "Cancel",
+            Action::Pause => "Pause",
+            Action::Hint => "Hint",
+            Action::Up => "Up",
+            Action::Down => "Down",
+            Action::Left => "Left",
+            Action::Right => "Right",
+            Action::Extend => "Extend Selection",
+        }
+    }
+
+    fn default_bindings(&self) -> Vec<Binding> {
+        use Binding::{Gamepad as Pad, Key, Mouse};
+
+        match self {
+            Action::Select => vec![Mouse(MouseButton::Left)],
+            Action::Confirm => vec![
+                Key(KeyCode::Enter),
+                Key(KeyCode::Space),
+                Pad(GamepadButton::South),
+            ],
+            Action::Cancel => vec![Key(KeyCode::Escape), Pad(GamepadButton::East)],
+            Action::Pause => vec![Key(KeyCode::Escape), Pad(GamepadButton::Start)],
+            Action::Hint => vec![Key(KeyCode::KeyH), Pad(GamepadButton::North)],
+            Action::Up => vec![
+                Key(KeyCode::ArrowUp),
+                Key(KeyCode::KeyW),
+                Pad(GamepadButton::DPadUp),
+            ],
+            Action::Down => vec![
+                Key(KeyCode::ArrowDown),
+                Key(KeyCode::KeyS),
+                Pad(GamepadButton::DPadDown),
+            ],
+            Action::Left => vec![
+                Key(KeyCode::ArrowLeft),
+                Key(KeyCode::KeyA),
+                Pad(GamepadButton::DPadLeft),
+            ],
+            Action::Right => vec![
+                Key(KeyCode::ArrowRight),
+                Key(KeyCode::KeyD),
+                Pad(GamepadButton::DPadRight),
+            ],
+            Action::Extend => vec![
+                Key(KeyCode::ShiftLeft),
+                Key(KeyCode::ShiftRight),
+                Pad(GamepadButton::LeftTrigger2),
+                Pad(GamepadButton::RightTrigger2),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+// only these can be bound, anything else is ignored while rebinding
+const KEYS: [KeyCode; 58] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Enter,
+    KeyCode::Space,
+    KeyCode::Escape,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+];
+
+const MOUSE_BUTTONS: [MouseButton; 3] =
+    [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+
+impl Binding {
+    fn all() -> impl Iterator<Item = Binding> {
+        KEYS.into_iter()
+            .map(Binding::Key)
+            .chain(MOUSE_BUTTONS.into_iter().map(Binding::Mouse))
+            .chain(GamepadButton::all().into_iter().map(Binding::Gamepad))
+    }
+
+    // rebinding a keyboard or mouse input keeps the gamepad ones and the other way round
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Gamepad(_))
+    }
+}
+
+// how bindings are written to the file, e.g. "Key(KeyW)" or "Gamepad(South)"
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:?}", self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Binding::all()
+            .find(|binding| format!("{:?}", binding) == name)
+            .ok_or_else(|| de::Error::custom(format!("unknown binding {}", name)))
+    }
+}
+
+// the name shown on the controls screen
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{:?}", key);
+                let name = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .or_else(|| name.strip_prefix("Arrow"))
+                    .unwrap_or(&name);
+                write!(f, "{}", name)
+            }
+            Binding::Mouse(button) => write!(f, "{:?} Click", button),
+            Binding::Gamepad(button) => write!(f, "Pad {:?}", button),
+        }
+    }
+}
+
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct Bindings(BTreeMap<Action, Vec<Binding>>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self(
+            Action::ALL
+                .iter()
+                .map(|action| (*action, action.default_bindings()))
+                .collect(),
+        )
+    }
+}
+
+impl Bindings {
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    // the new binding replaces the others from the same kind of device
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        bindings.retain(|bound| bound.is_gamepad() != binding.is_gamepad());
+        bindings.insert(0, binding);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BindingsFile {
+    version: u32,
+    #[serde(default, deserialize_with = "known_actions")]
+    bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+// a key in the bindings file, the name is kept when it isn't an action this build knows, e.g.
+// one from a newer version or a typo
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct ActionName(Result<Action, String>);
+
+impl<'de> Deserialize<'de> for ActionName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl de::Visitor<'_> for NameVisitor {
+            type Value = ActionName;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "an action name")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<ActionName, E> {
+                Ok(ActionName(
+                    Action::ALL
+                        .into_iter()
+                        .find(|action| format!("{:?}", action) == name)
+                        .ok_or_else(|| name.to_string()),
+                ))
+            }
+        }
+
+        deserializer.deserialize_identifier(NameVisitor)
+    }
+}
+
+// unknown actions are dropped one at a time rather than failing the whole file
+fn known_actions<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<Action, Vec<Binding>>, D::Error> {
+    let bindings = BTreeMap::<ActionName, Vec<Binding>>::deserialize(deserializer)?;
+
+    Ok(bindings
+        .into_iter()
+        .filter_map(|(ActionName(action), bound)| match action {
+            Ok(action) => Some((action, bound)),
+            Err(name) => {
+                warn!("ignoring bindings for unknown action {}", name);
+                None
+            }
+        })
+        .collect())
+}
+
+impl Default for BindingsFile {
+    fn default() -> Self {
+        Self {
+            version: BINDINGS_VERSION,
+            bindings: Bindings::default().0,
+        }
+    }
+}
+
+fn bindings_path() -> PathBuf {
+    config_dir().join("controls.ron")
+}
+
+// actions missing from the file keep their default bindings
+fn load_bindings() -> Bindings {
+    let file = load_ron::<BindingsFile>(&bindings_path());
+
+    if file.version > BINDINGS_VERSION {
+        warn!(
+            "controls file is version {} but this build only knows version {}, unknown actions are ignored",
+            file.version, BINDINGS_VERSION
+        );
+    }
+
+    let mut bindings = Bindings::default();
+    bindings.0.extend(file.bindings);
+    bindings
+}
+
+fn save_bindings(bindings: Res<Bindings>) {
+    let file = BindingsFile {
+        version: BINDINGS_VERSION,
+        bindings: bindings.0.clone(),
+    };
+
+    if let Err(err) = save_ron(&bindings_path(), &file) {
+        warn!("couldn't save controls: {}", err);
+    }
+}
+
+// which actions are held this frame, worked out from the bindings before anything in Update runs
+#[derive(Resource, Default)]
+pub struct ActionState {
+    pressed: HashSet<Action>,
+    just_pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+
+    // stops anything later in the frame reacting to the press
+    pub fn clear_just_pressed(&mut self, action: Action) {
+        self.just_pressed.remove(&action);
+    }
+
+    pub fn clear(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+    }
+}
+
+// run condition like `input_just_pressed`
+pub fn action_just_pressed(action: Action) -> impl FnMut(Res<ActionState>) -> bool + Clone {
+    move |actions: Res<ActionState>| actions.just_pressed(action)
+}
+
+// the keyboard, mouse and every connected gamepad, looked up through a binding
+#[derive(SystemParam)]
+pub struct RawInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl RawInput<'_, '_> {
+    fn pressed(&self, binding: &Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.pressed(*key),
+            Binding::Mouse(button) => self.mouse.pressed(*button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|pad| pad.pressed(*button)),
+        }
+    }
+
+    fn just_pressed(&self, binding: &Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.just_pressed(*key),
+            Binding::Mouse(button) => self.mouse.just_pressed(*button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|pad| pad.just_pressed(*button)),
+        }
+    }
+
+    fn just_released(&self, binding: &Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.just_released(*key),
+            Binding::Mouse(button) => self.mouse.just_released(*button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|pad| pad.just_released(*button)),
+        }
+    }
+}
+
+pub fn update_actions(bindings: Res<Bindings>, input: RawInput, mut actions: ResMut<ActionState>) {
+    let was_pressed = std::mem::take(&mut actions.pressed);
+    actions.clear();
+
+    for action in Action::ALL {
+        let bound = bindings.get(action);
+
+        let pressed = bound.iter().any(|binding| input.pressed(binding));
+        if pressed {
+            actions.pressed.insert(action);
+        }
+
+        if bound.iter().any(|binding| input.just_pressed(binding)) {
+            actions.just_pressed.insert(action);
+        }
+
+        // a tap can start and end within one frame, otherwise it's released once nothing holds it
+        if !pressed
+            && (was_pressed.contains(&action)
+                || bound.iter().any(|binding| input.just_released(binding)))
+        {
+            actions.just_released.insert(action);
+        }
+    }
+}
+
+// the first bindable input pressed this frame, used by the controls screen while it's listening. the
+// press is cleared so buttons and actions don't also react to it
+pub fn take_just_pressed_binding(
+    keys: &mut ButtonInput<KeyCode>,
+    mouse: &mut ButtonInput<MouseButton>,
+    gamepads: &mut Query<&mut Gamepad>,
+) -> Option<Binding> {
+    Binding::all().find(|binding| match binding {
+        Binding::Key(key) => keys.clear_just_pressed(*key),
+        Binding::Mouse(button) => mouse.clear_just_pressed(*button),
+        Binding::Gamepad(button) => gamepads
+            .iter_mut()
+            .any(|mut gamepad| gamepad.digital_mut().clear_just_pressed(*button)),
+    })
+}
@@ -0,0 +1,327 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::{
+    audio::{AddAudioSource, Decodable, PlaybackMode, Source, Volume},
+    prelude::*,
+};
+
+use crate::{loading::GameAssets, menu::settings::GameConfig, SystemState};
+
+// sound effects are generated tones so they need no files, the music is preloaded with the other
+// assets and falls back to generated tunes when the tracks aren't there
+pub fn audio_plugin(app: &mut App) {
+    app.add_audio_source::<Tune>()
+        .add_event::<Sfx>()
+        .init_resource::<AudioDevice>()
+        .add_systems(Startup, load_sounds)
+        .add_systems(OnEnter(SystemState::Menu), play_music)
+        .add_systems(OnEnter(SystemState::Game), play_music)
+        .add_systems(OnExit(SystemState::Menu), stop_music)
+        .add_systems(OnExit(SystemState::Game), stop_music)
+        .add_systems(
+            Update,
+            (
+                play_sfx.run_if(on_event::<Sfx>),
+                update_music_volume.run_if(resource_changed::<GameConfig>),
+                detect_missing_device,
+            ),
+        );
+}
+
+// the first pop in a chain plays the root, each link after it climbs the major scale
+const POP_ROOT_HZ: f32 = 523.25;
+const POP_SCALE: [f32; 8] = [0., 2., 4., 5., 7., 9., 11., 12.];
+const POP_SECS: f32 = 0.09;
+
+// a calm loop for the menu and a quicker one in game, as semitones above the root (None rests)
+// and their length in beats
+const MENU_TUNE: Tune = Tune {
+    root_hz: 261.63,
+    bpm: 84.,
+    notes: &[
+        (Some(0.), 1.),
+        (Some(4.), 1.),
+        (Some(7.), 1.),
+        (Some(9.), 1.),
+        (Some(7.), 2.),
+        (Some(4.), 2.),
+        (Some(2.), 1.),
+        (Some(4.), 1.),
+        (Some(7.), 1.),
+        (Some(4.), 1.),
+        (Some(2.), 2.),
+        (None, 2.),
+    ],
+};
+const GAME_TUNE: Tune = Tune {
+    root_hz: 220.,
+    bpm: 132.,
+    notes: &[
+        (Some(0.), 0.5),
+        (Some(3.), 0.5),
+        (Some(7.), 0.5),
+        (Some(10.), 0.5),
+        (Some(12.), 1.),
+        (Some(10.), 0.5),
+        (Some(7.), 0.5),
+        (Some(8.), 1.),
+        (Some(7.), 0.5),
+        (Some(5.), 0.5),
+        (Some(3.), 1.),
+        (None, 1.),
+        (Some(2.), 0.5),
+        (Some(3.), 0.5),
+        (Some(5.), 0.5),
+        (Some(7.), 0.5),
+        (Some(5.), 1.),
+        (Some(3.), 0.5),
+        (Some(2.), 0.5),
+        (Some(0.), 2.),
+        (None, 1.),
+    ],
+};
+const TUNE_SAMPLE_RATE: u32 = 44_100;
+const TUNE_ATTACK_SECS: f32 = 0.01;
+// quiet enough to sit under the sound effects at the same volume setting
+const TUNE_GAIN: f32 = 0.2;
+
+// a sound that hasn't found an output after this long never will
+const DEVICE_TIMEOUT_SECS: f32 = 1.;
+
+#[derive(Event, Clone, Copy, Debug)]
+pub enum Sfx {
+    Pop(i32), // the combo after the pop, so chains climb in pitch
+    Rejected,
+    Tick,
+    Click,
+}
+
+#[derive(Resource)]
+struct Sounds {
+    pops: Vec<Handle<Pitch>>,
+    rejected: Handle<Pitch>,
+    tick: Handle<Pitch>,
+    click: Handle<Pitch>,
+    menu_tune: Handle<Tune>,
+    game_tune: Handle<Tune>,
+}
+
+// a melody played as soft sine notes, what the music falls back to without the track files
+#[derive(Asset, TypePath, Clone)]
+struct Tune {
+    root_hz: f32,
+    bpm: f32,
+    notes: &'static [(Option<f32>, f32)],
+}
+
+impl Decodable for Tune {
+    type DecoderItem = f32;
+    type Decoder = TuneDecoder;
+
+    fn decoder(&self) -> TuneDecoder {
+        let samples_per_beat = TUNE_SAMPLE_RATE as f32 * 60. / self.bpm;
+
+        TuneDecoder {
+            notes: self
+                .notes
+                .iter()
+                .map(|(semitones, beats)| {
+                    let hz =
+                        semitones.map_or(0., |semitones| self.root_hz * 2f32.powf(semitones / 12.));
+                    (hz, (beats * samples_per_beat) as u32)
+                })
+                .collect(),
+            note: 0,
+            sample: 0,
+        }
+    }
+}
+
+struct TuneDecoder {
+    notes: Vec<(f32, u32)>, // frequency, 0 for a rest, and length in samples
+    note: usize,
+    sample: u32,
+}
+
+impl Iterator for TuneDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let &(hz, length) = self.notes.get(self.note)?;
+        let t = self.sample as f32 / TUNE_SAMPLE_RATE as f32;
+        let progress = self.sample as f32 / length as f32;
+
+        self.sample += 1;
+        if self.sample >= length {
+            self.note += 1;
+            self.sample = 0;
+        }
+
+        // a quick attack then a fade over the note so neighbours don't click into each other, with
+        // an octave below to fill it out
+        let envelope = (t / TUNE_ATTACK_SECS).min(1.) * (1. - progress).powi(2);
+        let wave = (TAU * hz * t).sin() + 0.4 * (TAU * hz / 2. * t).sin();
+        Some(wave * envelope * TUNE_GAIN)
+    }
+}
+
+impl Source for TuneDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        TUNE_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+// bevy only warns once when there is no output, so stop queueing sounds nobody can hear
+#[derive(Resource)]
+struct AudioDevice {
+    available: bool,
+}
+
+impl Default for AudioDevice {
+    fn default() -> Self {
+        Self { available: true }
+    }
+}
+
+#[derive(Component)]
+struct Music;
+
+#[derive(Component)]
+struct SfxPlayer {
+    spawned: f32, // elapsed seconds when it was queued
+}
+
+// master times the channel, both stored as percentages
+fn channel_volume(config: &GameConfig, channel: f32) -> f32 {
+    (config.master_volume / 100.) * (channel / 100.)
+}
+
+fn load_sounds(
+    mut commands: Commands,
+    mut pitches: ResMut<Assets<Pitch>>,
+    mut tunes: ResMut<Assets<Tune>>,
+) {
+    let mut tone = |hz: f32, secs: f32| pitches.add(Pitch::new(hz, Duration::from_secs_f32(secs)));
+
+    commands.insert_resource(Sounds {
+        pops: POP_SCALE
+            .iter()
+            .map(|semitones| tone(POP_ROOT_HZ * 2f32.powf(semitones / 12.), POP_SECS))
+            .collect(),
+        rejected: tone(140., 0.18),
+        tick: tone(1000., 0.03),
+        click: tone(660., 0.025),
+        menu_tune: tunes.add(MENU_TUNE),
+        game_tune: tunes.add(GAME_TUNE),
+    });
+}
+
+fn play_sfx(
+    mut commands: Commands,
+    mut sfx: EventReader<Sfx>,
+    sounds: Res<Sounds>,
+    config: Res<GameConfig>,
+    device: Res<AudioDevice>,
+    time: Res<Time>,
+) {
+    let volume = channel_volume(&config, config.sfx_volume);
+
+    if !device.available || volume <= 0. {
+        sfx.clear();
+        return;
+    }
+
+    for effect in sfx.read() {
+        let sound = match effect {
+            Sfx::Pop(combo) => sounds.pops[(*combo as usize).min(sounds.pops.len() - 1)].clone(),
+            Sfx::Rejected => sounds.rejected.clone(),
+            Sfx::Tick => sounds.tick.clone(),
+            Sfx::Click => sounds.click.clone(),
+        };
+
+        commands.spawn((
+            SfxPlayer {
+                spawned: time.elapsed_secs(),
+            },
+            AudioPlayer(sound),
+            PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+        ));
+    }
+}
+
+fn play_music(
+    mut commands: Commands,
+    system_state: Res<State<SystemState>>,
+    assets: Res<GameAssets>,
+    sounds: Res<Sounds>,
+    asset_server: Res<AssetServer>,
+    config: Res<GameConfig>,
+    device: Res<AudioDevice>,
+) {
+    if !device.available {
+        return;
+    }
+
+    let (file, tune) = match system_state.get() {
+        SystemState::Game => (&assets.game_music, &sounds.game_tune),
+        _ => (&assets.menu_music, &sounds.menu_tune),
+    };
+    let settings = PlaybackSettings {
+        mode: PlaybackMode::Loop,
+        volume: Volume::new(channel_volume(&config, config.music_volume)),
+        ..default()
+    };
+
+    // a track that failed to load was already reported by the loading screen
+    match file
+        .as_ref()
+        .filter(|file| !asset_server.load_state(*file).is_failed())
+    {
+        Some(file) => commands.spawn((Music, AudioPlayer(file.clone()), settings)),
+        None => commands.spawn((Music, AudioPlayer(tune.clone()), settings)),
+    };
+}
+
+fn stop_music(mut commands: Commands, music: Query<Entity, With<Music>>) {
+    for entity in &music {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update_music_volume(config: Res<GameConfig>, music: Query<&AudioSink, With<Music>>) {
+    for sink in &music {
+        sink.set_volume(channel_volume(&config, config.music_volume));
+    }
+}
+
+// tones are ready as soon as they're added, so one still without a sink means there's no output
+fn detect_missing_device(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut device: ResMut<AudioDevice>,
+    players: Query<(Entity, &SfxPlayer), Without<AudioSink>>,
+) {
+    for (entity, player) in &players {
+        if time.elapsed_secs() - player.spawned < DEVICE_TIMEOUT_SECS {
+            continue;
+        }
+
+        if device.available {
+            warn!("no audio output available, sound is turned off");
+            device.available = false;
+        }
+        commands.entity(entity).despawn_recursive();
+    }
+}
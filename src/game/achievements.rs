@@ -0,0 +1,239 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    despawn_screen,
+    storage::{data_dir, load_ron, save_ron, today},
+    theme::{ActiveTheme, Theme},
+    SystemState,
+};
+
+use super::{
+    playing::{GameFinishedEvent, TilesPoppedEvent},
+    squaregg::{GameMode, Squaregg},
+    InternalGameState,
+};
+
+pub fn achievements_plugin(app: &mut App) {
+    app.add_systems(Startup, load_achievements)
+        .add_systems(OnEnter(SystemState::Game), toast_container_setup)
+        .add_systems(
+            OnExit(SystemState::Game),
+            despawn_screen::<OnToastContainer>,
+        )
+        .add_systems(Update, fade_toasts.run_if(in_state(SystemState::Game)))
+        .add_observer(check_pop_achievements)
+        .add_observer(check_game_achievements);
+}
+
+// how long an unlock toast stays on screen
+const TOAST_SECS: f32 = 3.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstPop,
+    ThreeByThree,
+    ComboEight,
+    ClearBoard,
+    TimedFiveHundred,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstPop,
+        Achievement::ThreeByThree,
+        Achievement::ComboEight,
+        Achievement::ClearBoard,
+        Achievement::TimedFiveHundred,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::FirstPop => "Crack an Egg",
+            Achievement::ThreeByThree => "Square Deal",
+            Achievement::ComboEight => "Chain Reaction",
+            Achievement::ClearBoard => "Clean Plate",
+            Achievement::TimedFiveHundred => "Beat the Clock",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstPop => "Pop your first tiles",
+            Achievement::ThreeByThree => "Clear an area at least 3x3",
+            Achievement::ComboEight => "Reach combo 8",
+            Achievement::ClearBoard => "Clear the whole board",
+            Achievement::TimedFiveHundred => "Score 500 in Timed",
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Achievements {
+    pub unlocked: BTreeMap<Achievement, String>, // achievement to the date it was unlocked
+}
+
+impl Achievements {
+    fn path() -> PathBuf {
+        data_dir().join("achievements.ron")
+    }
+}
+
+#[derive(Component)]
+struct OnToastContainer;
+
+#[derive(Component)]
+struct Toast(Timer);
+
+fn load_achievements(mut commands: Commands) {
+    commands.insert_resource(load_ron::<Achievements>(&Achievements::path()));
+}
+
+fn toast_container_setup(mut commands: Commands) {
+    commands.spawn((
+        OnToastContainer,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.),
+            width: Val::Percent(100.),
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(10.),
+            ..default()
+        },
+        // above the results screen so end of game unlocks are still seen
+        ZIndex(100),
+    ));
+}
+
+fn check_pop_achievements(
+    _trigger: Trigger<TilesPoppedEvent>,
+    commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    achievements: ResMut<Achievements>,
+    toast_container: Query<Entity, With<OnToastContainer>>,
+    theme: Res<ActiveTheme>,
+) {
+    let game = &internal_game_state.0;
+    let area = &game.prev_area;
+
+    let earned = [
+        (Achievement::FirstPop, true),
+        (
+            Achievement::ThreeByThree,
+            area.width() >= 3 && area.height() >= 3,
+        ),
+        (Achievement::ComboEight, game.combo >= 8),
+        (Achievement::ClearBoard, board_cleared(game)),
+    ];
+
+    unlock(earned, commands, achievements, toast_container, &theme);
+}
+
+fn check_game_achievements(
+    _trigger: Trigger<GameFinishedEvent>,
+    commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    achievements: ResMut<Achievements>,
+    toast_container: Query<Entity, With<OnToastContainer>>,
+    theme: Res<ActiveTheme>,
+) {
+    let game = &internal_game_state.0;
+
+    let earned = [(
+        Achievement::TimedFiveHundred,
+        game.mode == GameMode::Timed && game.score >= 500,
+    )];
+
+    unlock(earned, commands, achievements, toast_container, &theme);
+}
+
+fn board_cleared(game: &Squaregg) -> bool {
+    game.board.iter().flatten().all(|tile| tile.is_none())
+}
+
+// records anything newly earned and toasts it
+fn unlock(
+    earned: impl IntoIterator<Item = (Achievement, bool)>,
+    mut commands: Commands,
+    mut achievements: ResMut<Achievements>,
+    toast_container: Query<Entity, With<OnToastContainer>>,
+    theme: &Theme,
+) {
+    let newly_unlocked: Vec<Achievement> = earned
+        .into_iter()
+        .filter(|(achievement, earned)| *earned && !achievements.unlocked.contains_key(achievement))
+        .map(|(achievement, _)| achievement)
+        .collect();
+
+    if newly_unlocked.is_empty() {
+        return;
+    }
+
+    newly_unlocked.iter().for_each(|achievement| {
+        achievements.unlocked.insert(*achievement, today());
+    });
+
+    if let Err(err) = save_ron(&Achievements::path(), &*achievements) {
+        warn!("couldn't save achievements: {}", err);
+    }
+
+    if let Ok(container) = toast_container.get_single() {
+        commands.entity(container).with_children(|parent| {
+            newly_unlocked
+                .iter()
+                .for_each(|achievement| spawn_toast(parent, achievement, theme));
+        });
+    }
+}
+
+fn spawn_toast(parent: &mut ChildBuilder<'_>, achievement: &Achievement, theme: &Theme) {
+    parent
+        .spawn((
+            Toast(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(3.)),
+                padding: UiRect::axes(Val::Px(20.), Val::Px(10.)),
+                ..default()
+            },
+            BackgroundColor(theme.panel),
+            BorderColor(theme.accent),
+            BorderRadius::all(Val::Px(10.)),
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text::new(format!(
+                "Achievement unlocked: {}",
+                achievement.title()
+            )));
+            parent.spawn((
+                Text::new(achievement.description()),
+                TextFont::from_font_size(14.),
+            ));
+        });
+}
+
+// toasts hold for most of their time then fade out over the last second
+fn fade_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts: Query<(Entity, &mut Toast, &mut BackgroundColor, &mut BorderColor)>,
+    theme: Res<ActiveTheme>,
+) {
+    for (entity, mut toast, mut background, mut border) in &mut toasts {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = toast.0.remaining_secs().min(1.);
+        background.0 = theme.panel.with_alpha(alpha);
+        border.0 = theme.accent.with_alpha(alpha);
+    }
+}
@@ -1,15 +1,37 @@
 use bevy::prelude::*;
 
-use crate::{despawn_screen, menu::spawn_button, SystemState};
+use crate::{
+    actions::{action_just_pressed, Action},
+    despawn_screen,
+    menu::{spawn_button, widgets::SettingWidget},
+    storage::today,
+    theme::ActiveTheme,
+    SystemState,
+};
 
-use super::GameState;
+use super::{
+    highscores::{spawn_high_score_table, table_key, HighScoreEntry, HighScores, PendingHighScore},
+    share::{share_results, SystemClipboard},
+    squaregg::GameStats,
+    GameState, InternalGameState,
+};
 
 pub fn finished_plugin(app: &mut App) {
-    app.add_systems(OnEnter(GameState::Finished), game_finished_setup)
+    app.init_resource::<SystemClipboard>()
+        .add_systems(OnEnter(GameState::Finished), game_finished_setup)
         .add_systems(OnExit(GameState::Finished), despawn_screen::<OnFinished>)
         .add_systems(
             Update,
-            (results_action, button_system).run_if(in_state(GameState::Finished)),
+            (
+                results_action,
+                button_system,
+                // confirm would also submit the name, so wait until that's done
+                play_again.run_if(
+                    action_just_pressed(Action::Confirm)
+                        .and(not(resource_exists::<PendingHighScore>)),
+                ),
+            )
+                .run_if(in_state(GameState::Finished)),
         );
 }
 
@@ -17,17 +39,77 @@ pub fn finished_plugin(app: &mut App) {
 #[derive(Component)]
 struct OnFinished;
 
-fn game_finished_setup(mut commands: Commands) {
+#[derive(Component)]
+struct ShareStatusText;
+
+fn game_finished_setup(
+    mut commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    mut high_scores: ResMut<HighScores>,
+    theme: Res<ActiveTheme>,
+) {
+    let game = &internal_game_state.0;
+    let key = table_key(game);
+
+    let entry = HighScoreEntry {
+        name: high_scores.player_name(),
+        date: today(),
+        seed: game.seed,
+        score: game.score,
+    };
+
+    let placed = match game.score > 0 {
+        true => high_scores.insert(key.clone(), entry),
+        false => None,
+    };
+
+    if let Some(index) = placed {
+        high_scores.save();
+        commands.insert_resource(PendingHighScore {
+            key: key.clone(),
+            index,
+            edited: false,
+        });
+    }
+
     commands
         .spawn((OnFinished, background()))
         .with_children(|parent| {
-            spawn_score(parent);
-            spawn_button(parent, ReultsButtonAction::Play, "Retry");
-            spawn_button(parent, ReultsButtonAction::Menu, "Menu");
-            spawn_button(parent, ReultsButtonAction::Share, "Share");
+            parent.spawn(row()).with_children(|parent| {
+                spawn_score(parent, game.score, &game.stats);
+                spawn_high_score_table(
+                    parent,
+                    &format!("High Scores - {}", key),
+                    high_scores
+                        .tables
+                        .get(&key)
+                        .map_or(&[], |table| table.as_slice()),
+                    placed,
+                    &theme,
+                );
+            });
+            if placed.is_some() {
+                parent.spawn(Text::new("New high score! Type your name and press Enter"));
+            }
+            parent.spawn(row()).with_children(|parent| {
+                spawn_button(parent, ReultsButtonAction::Play, "Retry");
+                spawn_button(parent, ReultsButtonAction::Menu, "Menu");
+                spawn_button(parent, ReultsButtonAction::Share, "Share");
+            });
+            parent.spawn((ShareStatusText, Text::default()));
         });
 }
 
+fn row() -> Node {
+    Node {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(60.),
+        ..default()
+    }
+}
+
 fn background() -> (Node, ZIndex, BackgroundColor) {
     (
         Node {
@@ -46,8 +128,47 @@ fn background() -> (Node, ZIndex, BackgroundColor) {
     )
 }
 
-fn spawn_score(parent: &mut ChildBuilder<'_>) {
-    parent.spawn(Text::new("good job?? or bad job nice"));
+fn spawn_score(parent: &mut ChildBuilder<'_>, score: i32, stats: &GameStats) {
+    let average_move = match stats.average_move_secs() {
+        Some(secs) => format!("{:.1}s", secs),
+        None => "-".to_string(),
+    };
+
+    parent
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Score: {}", score)),
+                TextFont::from_font_size(40.),
+            ));
+            parent.spawn(Text::new(format!(
+                "Tiles cleared: {} ({:.1}% of the board)",
+                stats.tiles_cleared,
+                stats.percent_cleared()
+            )));
+            parent.spawn(Text::new(format!(
+                "Moves: {}    Rejected: {}",
+                stats.moves, stats.rejected
+            )));
+            parent.spawn(Text::new(format!(
+                "Longest combo: {}    Largest area: {}",
+                stats.longest_combo, stats.largest_area
+            )));
+            parent.spawn(Text::new(format!(
+                "Average time per move: {}",
+                average_move
+            )));
+            parent.spawn(Text::new(format!(
+                "Tiles: {}  +  Area: {}  +  Combo: {}",
+                stats.score_from_tiles, stats.score_from_area, stats.score_from_combo
+            )));
+        });
 }
 
 // All actions that can be triggered from a button click
@@ -60,30 +181,31 @@ enum ReultsButtonAction {
 
 // Tag component used to mark which setting is currently selected
 #[derive(Component)]
-struct SelectedOption;
-
-const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
-const HOVERED_PRESSED_BUTTON: Color = Color::srgb(0.25, 0.65, 0.25);
-const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+pub(super) struct SelectedOption;
 
 // This system handles changing all buttons color based on mouse interaction
-fn button_system(
+pub(super) fn button_system(
     mut interaction_query: Query<
         (&Interaction, &mut BackgroundColor, Option<&SelectedOption>),
-        (Changed<Interaction>, With<Button>),
+        (Changed<Interaction>, With<Button>, Without<SettingWidget>),
     >,
+    theme: Res<ActiveTheme>,
 ) {
     for (interaction, mut image, selected) in &mut interaction_query {
         image.0 = match (*interaction, selected) {
-            (Interaction::Pressed, _) | (Interaction::None, Some(_)) => PRESSED_BUTTON,
-            (Interaction::Hovered, Some(_)) => HOVERED_PRESSED_BUTTON,
-            (Interaction::Hovered, None) => HOVERED_BUTTON,
-            (Interaction::None, None) => NORMAL_BUTTON,
+            (Interaction::Pressed, _) | (Interaction::None, Some(_)) => theme.button_pressed,
+            (Interaction::Hovered, Some(_)) => theme.button_hovered_pressed,
+            (Interaction::Hovered, None) => theme.button_hovered,
+            (Interaction::None, None) => theme.button,
         }
     }
 }
 
+// a shortcut for the play again button, cancel goes back to the menu
+fn play_again(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(GameState::Starting);
+}
+
 fn results_action(
     interaction_query: Query<
         (&Interaction, &ReultsButtonAction),
@@ -91,6 +213,9 @@ fn results_action(
     >,
     mut game_state: ResMut<NextState<GameState>>,
     mut system_state: ResMut<NextState<SystemState>>,
+    internal_game_state: Res<InternalGameState>,
+    mut share_status: Query<&mut Text, With<ShareStatusText>>,
+    mut clipboard: ResMut<SystemClipboard>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
@@ -101,7 +226,20 @@ fn results_action(
                 ReultsButtonAction::Play => {
                     game_state.set(GameState::Starting);
                 }
-                ReultsButtonAction::Share => { eprintln!("todo!()")},
+                ReultsButtonAction::Share => {
+                    let status = match share_results(&internal_game_state.0, &mut clipboard) {
+                        Ok(shared) if shared.copied_to_clipboard => format!(
+                            "Copied to clipboard and saved to {}",
+                            shared.folder.display()
+                        ),
+                        Ok(shared) => format!("Saved to {}", shared.folder.display()),
+                        Err(err) => format!("Couldn't share results: {}", err),
+                    };
+
+                    if let Ok(mut text) = share_status.get_single_mut() {
+                        text.0 = status;
+                    }
+                }
             }
         }
     }
@@ -1,23 +1,38 @@
 use bevy::{prelude::*, time::Stopwatch};
 use rand::random;
 
-use crate::game::GameState;
+use crate::{
+    despawn_screen,
+    game::{GameState, PauseState},
+    menu::settings::{GameConfig, PopStyle},
+};
 
 use super::Tile;
 
 pub fn animate_plugin(app: &mut App) {
-    app.add_systems(Update, animate_tiles.run_if(in_state(GameState::Playing)))
+    app.add_systems(Update, animate_tiles.run_if(in_state(PauseState::Running)))
+        .add_systems(
+            Update,
+            animate_particles.run_if(in_state(PauseState::Running)),
+        )
         .add_systems(Update, begin_tile_animation)
+        // popped tiles are no longer Tiles so the board cleanup doesn't catch them
+        .add_systems(
+            OnExit(GameState::Playing),
+            (
+                despawn_screen::<TileAnimating>,
+                despawn_screen::<PopParticle>,
+            ),
+        )
         .add_event::<StartTileAnimationEvent>();
 }
 
-const GRAVITY: f32 = 2000.;
-
 #[derive(Event)]
 pub struct StartTileAnimationEvent(pub Vec<(Entity, Transform)>);
 
 #[derive(Component)] // (stopwatch, x-velocity, y-velocity, x_start, y_start)
 struct TileAnimating {
+    style: PopStyle,
     stopwatch: Stopwatch,
     x_velocity: f32,
     y_velocity: f32,
@@ -25,6 +40,15 @@ struct TileAnimating {
     y_start: f32,
 }
 
+// one of the pieces a dissolving tile breaks into
+#[derive(Component)]
+struct PopParticle {
+    stopwatch: Stopwatch,
+    velocity: Vec2,
+    start: Vec2,
+    color: Color,
+}
+
 // pub fn start_tile_animation(mut commands: Commands, mut tiles: Vec<(Entity, &mut Transform)>) {
 //     tiles.iter_mut().for_each(|(e, t)| {
 //         commands.entity(*e).insert(TileAnimating {
@@ -42,54 +66,192 @@ struct TileAnimating {
 pub fn begin_tile_animation(
     mut ev_reader: EventReader<StartTileAnimationEvent>,
     mut commands: Commands,
+    config: Res<GameConfig>,
+    sprites: Query<&Sprite>,
 ) {
+    let style = config.effective_pop_style();
+    let params = &config.pop_params;
+
     for event in ev_reader.read() {
         event.0.iter().for_each(|(e, t)| {
-            commands.entity(*e).insert(TileAnimating {
-                stopwatch: Stopwatch::new(),
-                x_velocity: (random::<f32>() - 0.5) * 1200.,
-                y_velocity: random::<f32>() * 1200.,
-                x_start: t.translation.x,
-                y_start: t.translation.y,
-            });
+            // a popped tile can't be selected again while it's still on its way out
+            commands.entity(*e).remove::<Tile>();
+
+            match style {
+                PopStyle::None => {
+                    commands.entity(*e).despawn_recursive();
+                }
+                PopStyle::Dissolve => {
+                    let color = sprites
+                        .get(*e)
+                        .map(|sprite| sprite.color)
+                        .unwrap_or_default();
+                    let size = sprites
+                        .get(*e)
+                        .ok()
+                        .and_then(|sprite| sprite.custom_size)
+                        .unwrap_or(Vec2::splat(config.tile_size));
+                    spawn_particles(
+                        &mut commands,
+                        t.translation.truncate(),
+                        size,
+                        color,
+                        &config,
+                    );
+                    commands.entity(*e).despawn_recursive();
+                }
+                _ => {
+                    commands.entity(*e).insert(TileAnimating {
+                        style,
+                        stopwatch: Stopwatch::new(),
+                        x_velocity: (random::<f32>() - 0.5) * params.explode_speed,
+                        y_velocity: random::<f32>() * params.explode_speed,
+                        x_start: t.translation.x,
+                        y_start: t.translation.y,
+                    });
+                }
+            }
         });
     }
 }
 
+// breaks a tile into a grid of pieces that drift away from its centre
+fn spawn_particles(
+    commands: &mut Commands,
+    centre: Vec2,
+    tile_size: Vec2,
+    color: Color,
+    config: &GameConfig,
+) {
+    let per_side = config.pop_params.dissolve_particles.max(1);
+    let piece = tile_size / per_side as f32;
+
+    (0..per_side * per_side).for_each(|i| {
+        let offset = (Vec2::new((i % per_side) as f32, (i / per_side) as f32) + 0.5) * piece
+            - tile_size / 2.;
+        let jitter = Vec2::new(random::<f32>() - 0.5, random::<f32>() - 0.5);
+        let velocity = (offset.normalize_or_zero() + jitter) * config.pop_params.dissolve_speed;
+        let start = centre + offset;
+
+        commands.spawn((
+            PopParticle {
+                stopwatch: Stopwatch::new(),
+                velocity,
+                start,
+                color,
+            },
+            Sprite::from_color(color, piece),
+            Transform::from_translation(start.extend(0.5)),
+        ));
+    });
+}
+
 fn animate_tiles(
     mut commands: Commands,
     time: Res<Time>,
-    mut sprite_query: Query<(&mut TileAnimating, &mut Transform, Entity), With<Tile>>,
-    window: Query<&Window>,
+    config: Res<GameConfig>,
+    mut sprite_query: Query<(
+        &mut TileAnimating,
+        &mut Transform,
+        &mut Sprite,
+        Option<&Children>,
+        Entity,
+    )>,
+    mut children_query: Query<
+        (Option<&mut Sprite>, Option<&mut TextColor>),
+        Without<TileAnimating>,
+    >,
+    projection: Query<&OrthographicProjection, With<Camera2d>>,
 ) {
-    for (mut tile_animating, mut transform, entity) in &mut sprite_query {
+    let params = &config.pop_params;
+
+    for (mut tile_animating, mut transform, mut sprite, children, entity) in &mut sprite_query {
         tile_animating.stopwatch.tick(time.delta());
 
         let t = tile_animating.stopwatch.elapsed_secs();
 
-        // x = x0 + v * t
-        transform.translation.x = tile_animating.x_start + tile_animating.x_velocity * t;
+        match tile_animating.style {
+            PopStyle::Shrink => {
+                if t >= params.shrink_secs {
+                    commands.entity(entity).despawn_recursive();
+                    continue;
+                }
 
-        // y = y0 + v * t - g * (t ^ 2) / 2
-        transform.translation.y =
-            tile_animating.y_start + tile_animating.y_velocity * t - GRAVITY * t.powi(2) / 2.;
+                transform.scale = Vec3::splat(1. - t / params.shrink_secs);
+            }
+            PopStyle::Fade => {
+                if t >= params.fade_secs {
+                    commands.entity(entity).despawn_recursive();
+                    continue;
+                }
+
+                let alpha = 1. - t / params.fade_secs;
+                sprite.color.set_alpha(alpha);
+
+                // the number and outline fade with the tile
+                let mut child_iter = children_query.iter_many_mut(children.into_iter().flatten());
+                while let Some((child_sprite, child_text)) = child_iter.fetch_next() {
+                    if let Some(mut child_sprite) = child_sprite {
+                        child_sprite.color.set_alpha(alpha);
+                    }
+                    if let Some(mut child_text) = child_text {
+                        child_text.0.set_alpha(alpha);
+                    }
+                }
+            }
+            _ => {
+                // x = x0 + v * t
+                transform.translation.x = tile_animating.x_start + tile_animating.x_velocity * t;
 
-        transform.scale = Vec3::new(transform.scale.x + 0.01, transform.scale.y + 0.01, 0.);
+                // y = y0 + v * t - g * (t ^ 2) / 2
+                transform.translation.y = tile_animating.y_start + tile_animating.y_velocity * t
+                    - params.explode_gravity * t.powi(2) / 2.;
 
-        if let Ok(window) = window.get_single() {
-            let x_bounds = window.width() / 2.;
-            let y_bounds = window.height() / 2.;
+                let growth = params.explode_growth * time.delta_secs();
+                transform.scale =
+                    Vec3::new(transform.scale.x + growth, transform.scale.y + growth, 0.);
 
-            if transform.translation.y < -y_bounds
-                || transform.translation.x > x_bounds
-                || transform.translation.x < -x_bounds
-            {
-                commands.entity(entity).despawn_recursive();
+                // the visible world area rather than the window, the camera zooms out to fit the
+                // board on small windows
+                if let Ok(projection) = projection.get_single() {
+                    let bounds = projection.area;
+
+                    if transform.translation.y < bounds.min.y
+                        || transform.translation.x > bounds.max.x
+                        || transform.translation.x < bounds.min.x
+                    {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                } else {
+                    commands.entity(entity).despawn_recursive();
+                };
+
+                transform.translation.z = 100.; // put above ui elements and board
             }
-        } else {
+        }
+    }
+}
+
+fn animate_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut particles: Query<(Entity, &mut PopParticle, &mut Transform, &mut Sprite)>,
+) {
+    let secs = config.pop_params.dissolve_secs;
+
+    for (entity, mut particle, mut transform, mut sprite) in &mut particles {
+        let t = particle.stopwatch.tick(time.delta()).elapsed_secs();
+
+        if t >= secs {
             commands.entity(entity).despawn_recursive();
-        };
+            continue;
+        }
 
-        transform.translation.z = 100.; // put above ui elements and board
+        let progress = t / secs;
+        let position = particle.start + particle.velocity * t;
+        transform.translation = position.extend(transform.translation.z);
+        transform.scale = Vec3::splat(1. - progress * 0.5);
+        sprite.color = particle.color.with_alpha(1. - progress);
     }
 }
@@ -2,49 +2,22 @@ use bevy::prelude::*;
 
 use crate::{game::squaregg::{Area, COLS, ROWS}, menu::settings::GameConfig};
 
-pub struct RectBounds {
-    upper: f32,
-    lower: f32,
-    left: f32,
-    right: f32,
+// centre of the tile at `row` and `col`, row 0 being the bottom of the board
+pub fn cell_to_world(row: i32, col: i32, config: &GameConfig) -> Vec2 {
+    Vec2::new(
+        (0.5 + col as f32 - (COLS as f32 / 2.)) * (config.tile_size + config.tile_gap),
+        (0.5 + row as f32 - (ROWS as f32 / 2.)) * (config.tile_size + config.tile_gap),
+    )
 }
 
-impl RectBounds {
-    pub fn new(rect_transform: &Transform) -> Self {
-        let (left_bound, right_bound) = match (
-            rect_transform.translation.x,
-            rect_transform.translation.x - rect_transform.scale.x,
-        ) {
-            (a, b) if a < b => (b, a),
-            (a, b) => (a, b),
-        };
-
-        let (lower_bound, upper_bound) = match (
-            rect_transform.translation.y,
-            rect_transform.translation.y - rect_transform.scale.y,
-        ) {
-            (a, b) if a < b => (b, a),
-            (a, b) => (a, b),
-        };
-
-        return RectBounds {
-            upper: upper_bound,
-            lower: lower_bound,
-            left: left_bound,
-            right: right_bound,
-        };
-    }
-
-    pub fn contains(&self, transform_object: &Transform) -> bool {
-        transform_object.translation.x <= self.left
-            && transform_object.translation.x >= self.right
-            && transform_object.translation.y <= self.lower
-            && transform_object.translation.y >= self.upper
-    }
-
+// the cell under a world position, which may be off the board
+pub fn world_to_cell(position: Vec2, config: &GameConfig) -> (i32, i32) {
+    let cell = position / (config.tile_size + config.tile_gap)
+        + Vec2::new(COLS as f32 / 2., ROWS as f32 / 2.);
+    (cell.y.floor() as i32, cell.x.floor() as i32)
 }
 
-pub fn area_to_transform(area: &Area, config: &Res<GameConfig>) -> Transform {
+pub fn area_to_transform(area: &Area, config: &GameConfig) -> Transform {
     let translation = Vec2::new(
         (0.5 + (area.right + area.left) as f32 / 2. - (COLS as f32 / 2.))
             * (config.tile_size + config.tile_gap),
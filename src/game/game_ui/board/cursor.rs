@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState},
+    despawn_screen,
+    game::{
+        squaregg::{COLS, ROWS},
+        GameState, PauseState,
+    },
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+};
+
+use super::{
+    conversions::cell_to_world,
+    input::{apply_selection_input, SelectionInput},
+    BoardInput,
+};
+
+// a grid cursor so the game can be played from the keyboard alone
+pub fn cursor_plugin(app: &mut App) {
+    app.init_resource::<GridCursor>()
+        .add_systems(OnEnter(GameState::Playing), cursor_setup)
+        .add_systems(OnExit(GameState::Playing), despawn_screen::<CursorFrame>)
+        .add_systems(
+            Update,
+            (
+                hide_cursor_on_pointer,
+                move_cursor,
+                place_cursor,
+                restyle_cursor.run_if(resource_changed::<ActiveTheme>),
+            )
+                .chain()
+                .in_set(BoardInput)
+                .before(apply_selection_input)
+                .run_if(in_state(PauseState::Running)),
+        );
+}
+
+const FRAME_WIDTH: f32 = 3.;
+
+#[derive(Resource, Default)]
+pub struct GridCursor {
+    pub row: i32,
+    pub col: i32,
+    pub anchor: Option<(i32, i32)>, // the other corner while a rectangle is being grown
+    pub active: bool,               // hidden until a key is used, and again on a click or tap
+}
+
+impl GridCursor {
+    // moves by `rows` and `cols`, growing the selection from where it started when `extending`
+    pub fn step(
+        &mut self,
+        rows: i32,
+        cols: i32,
+        extending: bool,
+        config: &GameConfig,
+        selection: &mut EventWriter<SelectionInput>,
+    ) {
+        self.active = true;
+
+        if extending && self.anchor.is_none() {
+            self.anchor = Some((self.row, self.col));
+        } else if !extending && self.anchor.take().is_some() {
+            selection.send(SelectionInput::Cancel);
+        }
+
+        self.row = (self.row + rows).clamp(0, ROWS as i32 - 1);
+        self.col = (self.col + cols).clamp(0, COLS as i32 - 1);
+
+        self.send_rectangle(config, selection);
+    }
+
+    fn send_rectangle(&self, config: &GameConfig, selection: &mut EventWriter<SelectionInput>) {
+        if let Some((anchor_row, anchor_col)) = self.anchor {
+            selection.send(SelectionInput::Open(cell_to_world(
+                anchor_row, anchor_col, config,
+            )));
+            selection.send(SelectionInput::Extend(cell_to_world(
+                self.row, self.col, config,
+            )));
+        }
+    }
+
+    pub fn confirm(&mut self, selection: &mut EventWriter<SelectionInput>) -> bool {
+        let selecting = self.anchor.take().is_some();
+        if selecting {
+            selection.send(SelectionInput::Confirm);
+        }
+        selecting
+    }
+
+    pub fn cancel(&mut self, selection: &mut EventWriter<SelectionInput>) -> bool {
+        let selecting = self.anchor.take().is_some();
+        if selecting {
+            selection.send(SelectionInput::Cancel);
+        }
+        selecting
+    }
+}
+
+#[derive(Component)]
+struct CursorFrame;
+
+fn cursor_setup(mut commands: Commands, config: Res<GameConfig>, theme: Res<ActiveTheme>) {
+    commands.insert_resource(GridCursor {
+        row: ROWS as i32 / 2,
+        col: COLS as i32 / 2,
+        ..default()
+    });
+
+    let size = config.tile_size + config.tile_gap;
+    let offset = (size - FRAME_WIDTH) / 2.;
+
+    commands
+        .spawn((CursorFrame, Transform::default(), Visibility::Hidden))
+        .with_children(|frame| {
+            [
+                (Vec2::new(0., offset), Vec2::new(size, FRAME_WIDTH)),
+                (Vec2::new(0., -offset), Vec2::new(size, FRAME_WIDTH)),
+                (Vec2::new(offset, 0.), Vec2::new(FRAME_WIDTH, size)),
+                (Vec2::new(-offset, 0.), Vec2::new(FRAME_WIDTH, size)),
+            ]
+            .iter()
+            .for_each(|(position, edge)| {
+                frame.spawn((
+                    Sprite::from_color(theme.accent, *edge),
+                    Transform::from_translation(position.extend(0.)),
+                ));
+            });
+        });
+}
+
+fn hide_cursor_on_pointer(
+    actions: Res<ActionState>,
+    touches: Res<Touches>,
+    mut cursor: ResMut<GridCursor>,
+    mut selection: EventWriter<SelectionInput>,
+) {
+    if actions.just_pressed(Action::Select) || touches.any_just_pressed() {
+        cursor.cancel(&mut selection);
+        cursor.active = false;
+    }
+}
+
+pub(super) fn move_cursor(
+    mut actions: ResMut<ActionState>,
+    mut cursor: ResMut<GridCursor>,
+    config: Res<GameConfig>,
+    mut selection: EventWriter<SelectionInput>,
+) {
+    let extending = actions.pressed(Action::Extend);
+
+    // row 0 is the bottom of the board so up is +1
+    let direction = [
+        (Action::Up, (1, 0)),
+        (Action::Down, (-1, 0)),
+        (Action::Left, (0, -1)),
+        (Action::Right, (0, 1)),
+    ]
+    .into_iter()
+    .find(|(action, _)| actions.just_pressed(*action));
+
+    if let Some((_, (rows, cols))) = direction {
+        cursor.step(rows, cols, extending, &config, &mut selection);
+    }
+
+    if actions.just_pressed(Action::Confirm) {
+        cursor.confirm(&mut selection);
+    }
+
+    // escape is bound to both by default, so cancelling a selection shouldn't also pause the game
+    if actions.just_pressed(Action::Cancel) && cursor.cancel(&mut selection) {
+        actions.clear_just_pressed(Action::Pause);
+    }
+}
+
+fn place_cursor(
+    cursor: Res<GridCursor>,
+    config: Res<GameConfig>,
+    mut frame: Query<(&mut Transform, &mut Visibility), With<CursorFrame>>,
+) {
+    if let Ok((mut transform, mut visibility)) = frame.get_single_mut() {
+        transform.translation = cell_to_world(cursor.row, cursor.col, &config).extend(2.5);
+        *visibility = match cursor.active {
+            true => Visibility::Visible,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+fn restyle_cursor(
+    theme: Res<ActiveTheme>,
+    frame: Query<&Children, With<CursorFrame>>,
+    mut edges: Query<&mut Sprite>,
+) {
+    for children in &frame {
+        let mut edge_iter = edges.iter_many_mut(children);
+        while let Some(mut edge) = edge_iter.fetch_next() {
+            edge.color = theme.accent;
+        }
+    }
+}
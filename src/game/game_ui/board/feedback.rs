@@ -0,0 +1,122 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    despawn_screen,
+    game::{squaregg::Position, GameState, InternalGameState, PauseState},
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+};
+
+use super::{
+    conversions::cell_to_world,
+    input::{apply_selection_input, SelectionCells},
+    Rectangle, Tile,
+};
+
+// shows what a selection adds up to while it's still being dragged
+pub fn feedback_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Playing), spawn_selection_label)
+        .add_systems(OnExit(GameState::Playing), despawn_screen::<SelectionLabel>)
+        .add_systems(
+            Update,
+            selection_feedback
+                .after(apply_selection_input)
+                .run_if(in_state(PauseState::Running)),
+        );
+}
+
+const LABEL_SIZE: Vec2 = Vec2::new(110., 52.);
+// gap between the cell under the pointer and the label so it doesn't cover it
+const LABEL_OFFSET: Vec2 = Vec2::new(12., 12.);
+
+#[derive(Component)]
+struct SelectionLabel;
+
+#[derive(Component)]
+struct SelectionLabelText;
+
+fn spawn_selection_label(mut commands: Commands, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((
+            SelectionLabel,
+            Sprite {
+                color: theme.panel,
+                custom_size: Some(LABEL_SIZE),
+                anchor: Anchor::BottomLeft,
+                ..default()
+            },
+            Transform::from_xyz(0., 0., 4.),
+            Visibility::Hidden,
+        ))
+        .with_child((
+            SelectionLabelText,
+            Text2d::default(),
+            TextFont::from_font_size(18.),
+            TextLayout::new_with_justify(JustifyText::Center),
+            Transform::from_translation((LABEL_SIZE / 2.).extend(0.1)),
+        ));
+}
+
+fn selection_feedback(
+    internal_game_state: Res<InternalGameState>,
+    cells: Res<SelectionCells>,
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
+    tiles: Query<&Tile>,
+    mut rectangle: Query<(&Visibility, &mut Sprite), With<Rectangle>>,
+    mut label: Query<
+        (&mut Transform, &mut Visibility, &mut Sprite),
+        (With<SelectionLabel>, Without<Rectangle>),
+    >,
+    mut label_text: Query<(&mut Text2d, &mut TextColor), With<SelectionLabelText>>,
+) {
+    let (
+        Ok((rect_visibility, mut rect_sprite)),
+        Ok((mut label_transform, mut label_visibility, mut label_sprite)),
+        Ok((mut text, mut text_color)),
+    ) = (
+        rectangle.get_single_mut(),
+        label.get_single_mut(),
+        label_text.get_single_mut(),
+    )
+    else {
+        return;
+    };
+
+    let selected: Vec<Position> = tiles
+        .iter()
+        .filter(|tile| tile.selected)
+        .map(|tile| Position {
+            row: tile.row as usize,
+            col: tile.col as usize,
+        })
+        .collect();
+
+    let (sum, score) = internal_game_state.0.preview_move(&selected);
+
+    // the tile colours already carry meaning, so these stay readable with the accessibility palettes
+    let (rect_color, label_color) = match sum {
+        10 => (theme.tile_selected, theme.tile_selected),
+        s if s > 10 => (theme.warning, theme.warning),
+        _ => (theme.selection_rectangle, theme.text),
+    };
+    rect_sprite.color = rect_color.with_alpha(theme.selection_rectangle.alpha());
+
+    *label_visibility = match (*rect_visibility, selected.is_empty()) {
+        (Visibility::Hidden, _) | (_, true) => Visibility::Hidden,
+        _ => Visibility::Visible,
+    };
+
+    let (row, col) = cells.corner;
+    let cell_corner =
+        cell_to_world(row, col, &config) + Vec2::splat((config.tile_size + config.tile_gap) / 2.);
+    label_transform.translation =
+        (cell_corner + LABEL_OFFSET).extend(label_transform.translation.z);
+
+    label_sprite.color = theme.panel;
+    let label = format!("{} / 10\n+{}", sum, score);
+    if text.0 != label {
+        text.0 = label;
+    }
+    text_color.0 = label_color;
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, ActionState},
+    game::PauseState,
+    menu::settings::GameConfig,
+};
+
+use super::{
+    cursor::{move_cursor, GridCursor},
+    input::{apply_selection_input, SelectionInput},
+    BoardInput,
+};
+
+// the left stick moves the grid cursor, repeating while held, buttons and the d-pad go through
+// the bindings like any other key
+pub fn gamepad_plugin(app: &mut App) {
+    app.init_resource::<StickRepeat>().add_systems(
+        Update,
+        gamepad_cursor
+            .in_set(BoardInput)
+            .after(move_cursor)
+            .before(apply_selection_input)
+            .run_if(in_state(PauseState::Running)),
+    );
+}
+
+// how far the stick has to be pushed before it moves the cursor
+const STICK_DEADZONE: f32 = 0.5;
+// while the stick is held the cursor keeps stepping this often
+const STICK_REPEAT_SECS: f32 = 0.15;
+
+#[derive(Resource)]
+struct StickRepeat {
+    direction: IVec2,
+    timer: Timer,
+}
+
+impl Default for StickRepeat {
+    fn default() -> Self {
+        Self {
+            direction: IVec2::ZERO,
+            timer: Timer::from_seconds(STICK_REPEAT_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+// the stick snapped to whichever axis it is pushed furthest along, as (rows, cols)
+fn stick_direction(stick: Vec2) -> IVec2 {
+    if stick.length() < STICK_DEADZONE {
+        IVec2::ZERO
+    } else if stick.x.abs() > stick.y.abs() {
+        IVec2::new(0, stick.x.signum() as i32)
+    } else {
+        IVec2::new(stick.y.signum() as i32, 0)
+    }
+}
+
+fn gamepad_cursor(
+    time: Res<Time>,
+    gamepads: Query<&Gamepad>,
+    actions: Res<ActionState>,
+    config: Res<GameConfig>,
+    mut cursor: ResMut<GridCursor>,
+    mut repeat: ResMut<StickRepeat>,
+    mut selection: EventWriter<SelectionInput>,
+) {
+    let extending = actions.pressed(Action::Extend);
+
+    // several pads on the couch all share the one cursor
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| stick_direction(gamepad.left_stick()))
+        .find(|direction| *direction != IVec2::ZERO)
+        .unwrap_or(IVec2::ZERO);
+
+    if stick != repeat.direction {
+        repeat.direction = stick;
+        repeat.timer.reset();
+        if stick != IVec2::ZERO {
+            cursor.step(stick.x, stick.y, extending, &config, &mut selection);
+        }
+    } else if stick != IVec2::ZERO && repeat.timer.tick(time.delta()).just_finished() {
+        cursor.step(stick.x, stick.y, extending, &config, &mut selection);
+    }
+}
@@ -1,138 +1,365 @@
-use bevy::{
-    input::common_conditions::{input_just_pressed, input_just_released, input_pressed},
-    prelude::*,
-    window::PrimaryWindow,
-};
+use bevy::{prelude::*, window::PrimaryWindow};
 
-use crate::game::{playing::TilesPoppedEvent, squaregg::Position, GameState, InternalGameState};
+use crate::{
+    actions::{Action, ActionState},
+    audio::Sfx,
+    game::{
+        playing::TilesPoppedEvent,
+        squaregg::{Area, Position, COLS, ROWS},
+        InternalGameState, PauseState,
+    },
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+};
 
-use super::{animate_tiles::StartTileAnimationEvent, conversions::RectBounds, Rectangle, Tile};
+use super::{
+    animate_tiles::StartTileAnimationEvent,
+    conversions::{area_to_transform, world_to_cell},
+    BoardInput, Rectangle, Tile,
+};
 
 pub fn input_plugin(app: &mut App) {
-    app.add_systems(
-        Update,
-        (
-            open_rectangle.run_if(input_just_pressed(MouseButton::Left)),
-            extend_rectangle.run_if(input_pressed(MouseButton::Left)),
-            close_rectangle.run_if(input_just_released(MouseButton::Left)),
-        )
-            .chain()
-            .run_if(in_state(GameState::Playing)),
-    );
+    app.add_event::<SelectionInput>()
+        .init_resource::<SelectionCells>()
+        .add_systems(
+            Update,
+            (mouse_selection, touch_selection, apply_selection_input)
+                .chain()
+                .in_set(BoardInput)
+                .run_if(in_state(PauseState::Running)),
+        );
+}
+
+// every input device drives the selection rectangle through these, positions are in world space
+// and snapped to the cell under them
+#[derive(Event)]
+pub enum SelectionInput {
+    Open(Vec2),   // anchors one corner of the rectangle
+    Extend(Vec2), // moves the opposite corner
+    Confirm,      // tries to pop whatever is inside
+    Cancel,       // drops the selection without it counting as a move
+}
+
+// the selection snapped to board cells, it starts at `anchor` and follows the pointer to `corner`
+#[derive(Resource, Default)]
+pub struct SelectionCells {
+    pub anchor: (i32, i32),
+    pub corner: (i32, i32),
+}
+
+impl SelectionCells {
+    // the cells covered, trimmed to the board, or none when the drag is entirely off it
+    pub fn area(&self) -> Option<Area> {
+        let area = Area {
+            upper: self.anchor.0.max(self.corner.0).min(ROWS as i32 - 1),
+            lower: self.anchor.0.min(self.corner.0).max(0),
+            left: self.anchor.1.min(self.corner.1).max(0),
+            right: self.anchor.1.max(self.corner.1).min(COLS as i32 - 1),
+        };
+
+        (area.lower <= area.upper && area.left <= area.right).then_some(area)
+    }
+}
+
+type RectangleQuery<'w, 's> =
+    Query<'w, 's, (&'static mut Visibility, &'static mut Transform), With<Rectangle>>;
+type TileQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut Tile,
+        &'static Transform,
+        &'static mut Sprite,
+    ),
+    Without<Rectangle>,
+>;
+
+type CameraQuery<'w, 's> =
+    Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<Camera2d>>;
+
+// holding a finger still for this long drops the selection
+const LONG_PRESS_SECS: f32 = 0.6;
+// how far a finger can wander, in logical pixels, and still count as holding still
+const LONG_PRESS_SLOP: f32 = 12.;
+
+// the finger currently drawing the rectangle, other fingers are ignored until it lifts
+#[derive(Default)]
+struct TouchGesture {
+    finger: Option<u64>,
+    held_secs: f32,
+    cancelled: bool,
+}
+
+fn to_world(camera_q: &CameraQuery, position: Vec2) -> Option<Vec2> {
+    let (camera, camera_transform) = camera_q.single();
+    camera.viewport_to_world_2d(camera_transform, position).ok()
 }
 
-fn open_rectangle(
-    mut rectangle: Query<(&mut Visibility, &mut Transform), With<Rectangle>>,
+fn mouse_selection(
+    actions: Res<ActionState>,
     windows: Query<&Window, With<PrimaryWindow>>,
-    camera_q: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    camera_q: CameraQuery,
+    mut selection: EventWriter<SelectionInput>,
 ) {
-    let (camera, camera_transform) = camera_q.single();
-    if let Some(position) = windows
+    let position = windows
         .single()
         .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
-    {
-        if let Ok((mut visibility, mut transform)) = rectangle.get_single_mut() {
-            transform.translation = position.extend(1.0);
-            *visibility = Visibility::Visible;
-        }
+        .and_then(|cursor| to_world(&camera_q, cursor));
+
+    if let (true, Some(position)) = (actions.just_pressed(Action::Select), position) {
+        selection.send(SelectionInput::Open(position));
+    }
+
+    if let (true, Some(position)) = (actions.pressed(Action::Select), position) {
+        selection.send(SelectionInput::Extend(position));
+    }
+
+    if actions.just_released(Action::Select) {
+        selection.send(SelectionInput::Confirm);
     }
 }
 
-fn extend_rectangle(
-    mut rectangle: Query<(&mut Visibility, &mut Transform), With<Rectangle>>,
-    mut tiles: Query<(&Tile, &Transform, &mut Sprite), Without<Rectangle>>,
-    windows: Query<&Window, With<PrimaryWindow>>,
-    camera_q: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+// a single finger drags the rectangle out like the mouse does, a long press or a second finger
+// cancels it
+fn touch_selection(
+    time: Res<Time>,
+    touches: Res<Touches>,
+    camera_q: CameraQuery,
+    mut gesture: Local<TouchGesture>,
+    mut selection: EventWriter<SelectionInput>,
 ) {
-    let (camera, camera_transform) = camera_q.single();
-    if let Some(position) = windows
-        .single()
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
-    {
-        if let Ok((_, mut transform)) = rectangle.get_single_mut() {
-            transform.scale = Vec3::new(
-                transform.translation.x - position.x,
-                transform.translation.y - position.y,
-                1.0,
-            );
-
-            let bounds = RectBounds::new(&transform);
-
-            tiles
-                .iter_mut()
-                .for_each(|(_, tile_transform, mut sprite)| {
-                    if bounds.contains(tile_transform) {
-                        sprite.color = Color::srgb(0.20, 0.8, 0.70)
-                    } else {
-                        sprite.color = Color::WHITE
-                    }
-                });
+    for touch in touches.iter_just_pressed() {
+        if gesture.finger.is_none() {
+            *gesture = TouchGesture {
+                finger: Some(touch.id()),
+                ..default()
+            };
+            if let Some(position) = to_world(&camera_q, touch.position()) {
+                selection.send(SelectionInput::Open(position));
+            }
+        } else if !gesture.cancelled {
+            gesture.cancelled = true;
+            selection.send(SelectionInput::Cancel);
+        }
+    }
+
+    let Some(finger) = gesture.finger else {
+        return;
+    };
+
+    // once cancelled the rest of the gesture is ignored until the finger lifts
+    if let (Some(touch), false) = (touches.get_pressed(finger), gesture.cancelled) {
+        gesture.held_secs += time.delta_secs();
 
-            // *visibility = Visibility::Visible;
+        if gesture.held_secs >= LONG_PRESS_SECS && touch.distance().length() < LONG_PRESS_SLOP {
+            gesture.cancelled = true;
+            selection.send(SelectionInput::Cancel);
+        } else if let Some(position) = to_world(&camera_q, touch.position()) {
+            selection.send(SelectionInput::Extend(position));
         }
     }
+
+    if touches.just_released(finger) || touches.just_canceled(finger) {
+        if !gesture.cancelled {
+            selection.send(match touches.just_released(finger) {
+                true => SelectionInput::Confirm,
+                false => SelectionInput::Cancel,
+            });
+        }
+        *gesture = TouchGesture::default();
+    }
 }
 
-fn close_rectangle(
-    // ev_writer_poptiles : EventWriter<TilesPoppedEvent>,
+pub(super) fn apply_selection_input(
+    mut selection: EventReader<SelectionInput>,
     mut commands: Commands,
-    mut rectangle: Query<(&mut Visibility, &mut Transform), With<Rectangle>>,
-    mut tiles: Query<(Entity, &Transform, &mut Sprite, &Tile), Without<Rectangle>>,
+    mut rectangle: RectangleQuery,
+    mut tiles: TileQuery,
     mut internal_game_state: ResMut<InternalGameState>,
     mut ev_writer: EventWriter<StartTileAnimationEvent>,
+    mut cells: ResMut<SelectionCells>,
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
 ) {
-    if let Ok((mut visibility, transform)) = rectangle.get_single_mut() {
+    for input in selection.read() {
+        match input {
+            SelectionInput::Open(position) => {
+                let cell = world_to_cell(*position, &config);
+                cells.anchor = cell;
+                open_rectangle(&mut rectangle);
+                extend_rectangle(
+                    &mut rectangle,
+                    &mut tiles,
+                    &mut cells,
+                    cell,
+                    &config,
+                    &theme,
+                )
+            }
+            SelectionInput::Extend(position) => {
+                let cell = world_to_cell(*position, &config);
+                extend_rectangle(
+                    &mut rectangle,
+                    &mut tiles,
+                    &mut cells,
+                    cell,
+                    &config,
+                    &theme,
+                )
+            }
+            SelectionInput::Confirm => close_rectangle(
+                &mut commands,
+                &mut rectangle,
+                &mut tiles,
+                &cells,
+                &mut internal_game_state,
+                &mut ev_writer,
+                &theme,
+            ),
+            SelectionInput::Cancel => cancel_rectangle(&mut rectangle, &mut tiles, &theme),
+        }
+    }
+}
+
+fn open_rectangle(rectangle: &mut RectangleQuery) {
+    if let Ok((mut visibility, _)) = rectangle.get_single_mut() {
+        *visibility = Visibility::Visible;
+    }
+}
+
+fn extend_rectangle(
+    rectangle: &mut RectangleQuery,
+    tiles: &mut TileQuery,
+    cells: &mut SelectionCells,
+    cell: (i32, i32),
+    config: &GameConfig,
+    theme: &ActiveTheme,
+) {
+    cells.corner = cell;
+    let area = cells.area();
+
+    if let Ok((_, mut transform)) = rectangle.get_single_mut() {
+        // the sprite is anchored at its top right, so it's placed there and scaled out over the cells
+        let (corner, size) = match &area {
+            Some(area) => {
+                let covered = area_to_transform(area, config);
+                (
+                    covered.translation.truncate() + covered.scale.truncate() / 2.,
+                    covered.scale.truncate(),
+                )
+            }
+            None => (transform.translation.truncate(), Vec2::ZERO),
+        };
+        transform.translation = corner.extend(1.0);
+        transform.scale = size.extend(1.0);
+
+        tiles.iter_mut().for_each(|(_, mut tile, _, mut sprite)| {
+            let selected = area
+                .as_ref()
+                .is_some_and(|area| area.contains(tile.row, tile.col));
+            if selected {
+                sprite.color = theme.tile_selected
+            } else {
+                sprite.color = theme.tile
+            }
+            // only touch it when it flips so outlines are updated just for those tiles
+            if tile.selected != selected {
+                tile.selected = selected;
+            }
+        });
+    }
+}
+
+fn close_rectangle(
+    commands: &mut Commands,
+    rectangle: &mut RectangleQuery,
+    tiles: &mut TileQuery,
+    cells: &SelectionCells,
+    internal_game_state: &mut InternalGameState,
+    ev_writer: &mut EventWriter<StartTileAnimationEvent>,
+    theme: &ActiveTheme,
+) {
+    if let Ok((mut visibility, _)) = rectangle.get_single_mut() {
+        // nothing to pop, the selection was cancelled or the board hidden part way through
+        if *visibility == Visibility::Hidden {
+            return;
+        }
         *visibility = Visibility::Hidden;
 
-        let bounds = RectBounds::new(&transform);
+        let area = cells.area();
 
         let mut tiles_selected: Vec<_> = tiles
             .iter_mut()
-            .filter(|(_, tile_transform, _, _)| bounds.contains(tile_transform))
+            .filter(|(_, tile, _, _)| {
+                area.as_ref()
+                    .is_some_and(|area| area.contains(tile.row, tile.col))
+            })
             .collect();
 
+        tiles_selected
+            .iter_mut()
+            .for_each(|(_, tile, _, _)| tile.selected = false);
+
         if tiles_selected.len() > 10 {
+            internal_game_state.0.reject_move();
+            commands.send_event(Sfx::Rejected);
+
             // set selected tiles back to default state
             tiles_selected
                 .iter_mut()
-                .for_each(|(_, _, sprite, _)| sprite.color = Color::WHITE);
+                .for_each(|(_, _, _, sprite)| sprite.color = theme.tile);
             return;
         }
 
-        let tile_positions = &tiles_selected
+        let tile_positions: Vec<Position> = tiles_selected
             .iter()
-            .map(|(_, _, _, tile)| Position {
+            .map(|(_, tile, _, _)| Position {
                 row: tile.row as usize,
                 col: tile.col as usize,
             })
             .collect();
 
-        match internal_game_state.0.try_pop_tiles(
-            &tile_positions,
-            // ev_writer_poptiles,
-            // commands,
-        ) {
+        match internal_game_state.0.try_pop_tiles(&tile_positions) {
             true => {
                 // set selected tiles to animating on popped state
                 commands.trigger(TilesPoppedEvent {
                     tiles: tile_positions.to_vec(),
                 });
+                commands.send_event(Sfx::Pop(internal_game_state.0.combo));
 
                 ev_writer.send(StartTileAnimationEvent(
                     tiles_selected
                         .iter()
-                        .map(|(entity, transform, _, _)| (*entity, **transform))
+                        .map(|(entity, _, transform, _)| (*entity, **transform))
                         .collect(),
                 ));
             }
             false => {
+                // an empty selection isn't a move so it stays quiet
+                if !tile_positions.is_empty() {
+                    commands.send_event(Sfx::Rejected);
+                }
+
                 // set selected tiles back to default state
                 tiles_selected
                     .iter_mut()
-                    .for_each(|(_, _, sprite, _)| sprite.color = Color::WHITE);
+                    .for_each(|(_, _, _, sprite)| sprite.color = theme.tile);
             }
         }
     }
 }
+
+fn cancel_rectangle(rectangle: &mut RectangleQuery, tiles: &mut TileQuery, theme: &ActiveTheme) {
+    if let Ok((mut visibility, _)) = rectangle.get_single_mut() {
+        *visibility = Visibility::Hidden;
+    }
+
+    tiles
+        .iter_mut()
+        .filter(|(_, tile, _, _)| tile.selected)
+        .for_each(|(_, mut tile, _, mut sprite)| {
+            tile.selected = false;
+            sprite.color = theme.tile;
+        });
+}
@@ -2,26 +2,62 @@ use animate_tiles::StartTileAnimationEvent;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use conversions::area_to_transform;
+use patterns::{TileOutline, OUTLINE_WIDTH};
 
 use crate::game::playing::TilesPoppedEvent;
 use crate::game::squaregg::{COLS, ROWS};
-use crate::game::GameState;
+use crate::game::starting::StartingTimer;
+use crate::game::{reset_board, GameState, PauseState};
 use crate::menu::settings::GameConfig;
+use crate::theme::ActiveTheme;
 use crate::game::InternalGameState;
+use crate::SystemState;
 
 mod animate_tiles;
 mod conversions;
+mod cursor;
+mod feedback;
+mod gamepad;
 mod input;
+mod patterns;
+mod popups;
+mod trail;
 
 pub fn board_plugin(app: &mut App) {
-    // if board_setup scheduled on OnEnter(SystemState::Playing), tiles might render previous board
-    app.add_systems(OnEnter(GameState::Playing), board_setup)
+    // set up while the countdown runs so it can fade in, but only once reset_board has dealt the
+    // new board or the tiles would show the previous one
+    app.add_systems(OnEnter(GameState::Starting), board_setup.after(reset_board))
         .add_systems(OnExit(GameState::Playing), board_cleanup)
-        .add_plugins((input::input_plugin, animate_tiles::animate_plugin))
+        // leaving during the countdown never reaches playing
+        .add_systems(OnExit(SystemState::Game), board_cleanup)
+        .add_systems(Update, reveal_board.run_if(in_state(GameState::Starting)))
+        .add_systems(OnExit(GameState::Starting), finish_reveal)
+        // hide the board while paused so nobody can plan their next move
+        .add_systems(OnExit(PauseState::Running), hide_board)
+        .add_systems(OnEnter(PauseState::Running), show_board)
+        .add_systems(
+            Update,
+            restyle_board
+                .run_if(resource_changed::<ActiveTheme>.and(in_state(GameState::Playing))),
+        )
+        .add_plugins((
+            input::input_plugin,
+            cursor::cursor_plugin,
+            gamepad::gamepad_plugin,
+            feedback::feedback_plugin,
+            animate_tiles::animate_plugin,
+            patterns::patterns_plugin,
+            popups::popups_plugin,
+            trail::trail_plugin,
+        ))
         .add_observer(observe_poptiles_event);
         // .add_systems(Update, observe_poptiles_event);
 }
 
+// everything that reads gameplay input, so other systems can run after it has had first pick
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoardInput;
+
 #[derive(Component)]
 pub struct Rectangle;
 
@@ -32,6 +68,7 @@ pub struct PrevRectangle;
 struct Tile {
     row: i32,
     col: i32,
+    selected: bool,
 }
 
 fn board_setup(
@@ -39,10 +76,11 @@ fn board_setup(
     asset_server: Res<AssetServer>,
     internal_game_state: Res<InternalGameState>,
     config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
 ) {
-    spawn_tiles(&mut commands, asset_server, internal_game_state, config);
-    spawn_rectangle(&mut commands);
-    spawn_prev_rectangle(&mut commands);
+    spawn_tiles(&mut commands, asset_server, internal_game_state, config, &theme);
+    spawn_rectangle(&mut commands, &theme);
+    spawn_prev_rectangle(&mut commands, &theme);
 }
 
 fn spawn_tiles(
@@ -50,6 +88,7 @@ fn spawn_tiles(
     asset_server: Res<AssetServer>,
     internal_game_state: Res<InternalGameState>,
     config: Res<GameConfig>,
+    theme: &ActiveTheme,
 ) {
     let offset_x = -((COLS - 1) as f32) * (config.tile_size + config.tile_gap) / 2.;
     let offset_y = -((ROWS - 1) as f32) * (config.tile_size + config.tile_gap) / 2.;
@@ -69,7 +108,7 @@ fn spawn_tiles(
 
                     commands
                         .spawn((
-                            Sprite::from_color(Color::hsl(0.2, 0.2, 0.9), Vec2::new(
+                            Sprite::from_color(theme.tile, Vec2::new(
                                 config.tile_size,
                                 config.tile_size,
                             )),
@@ -89,9 +128,19 @@ fn spawn_tiles(
                             Tile {
                                 row: row_index as i32,
                                 col: col_index as i32,
+                                selected: false,
                             },
                         ))
                         .with_children(|builder| {
+                            builder.spawn((
+                                Sprite::from_color(
+                                    theme.outline,
+                                    Vec2::splat(config.tile_size + 2. * OUTLINE_WIDTH),
+                                ),
+                                Transform::from_xyz(0., 0., -0.5),
+                                Visibility::Hidden,
+                                TileOutline,
+                            ));
                             builder.spawn(
                                 (
                                     Text2d::new(val.to_string()),
@@ -116,7 +165,7 @@ fn spawn_tiles(
         });
 }
 
-fn spawn_rectangle(commands: &mut Commands) {
+fn spawn_rectangle(commands: &mut Commands, theme: &ActiveTheme) {
     commands.spawn((
         // SpriteBundle {
         //     transform: Transform {
@@ -132,7 +181,8 @@ fn spawn_rectangle(commands: &mut Commands) {
         //     visibility: Visibility::Hidden,
         //     ..default()
         // },
-        Sprite::from_color(Color::srgba(0.9, 0.8, 0.7, 0.2), Vec2::new(500., 500.)),
+        // sized by its transform scale
+        Sprite::from_color(theme.selection_rectangle, Vec2::ONE),
         Transform::from_xyz(0., 0., 3.),
         Visibility::Hidden,
         Anchor::TopRight,
@@ -140,7 +190,7 @@ fn spawn_rectangle(commands: &mut Commands) {
     ));
 }
 
-fn spawn_prev_rectangle(commands: &mut Commands) {
+fn spawn_prev_rectangle(commands: &mut Commands, theme: &ActiveTheme) {
     commands.spawn((
         // SpriteBundle {
         //     transform: Transform::from_xyz(0., 0., 1.).with_scale(Vec3::new(0., 0., 1.)),
@@ -151,7 +201,8 @@ fn spawn_prev_rectangle(commands: &mut Commands) {
         //     visibility: Visibility::Hidden,
         //     ..default()
         // },
-        Sprite::from_color(Color::srgba(0.9, 0.8, 0.7, 0.2), Vec2::new(0., 0.)),
+        // sized by its transform scale
+        Sprite::from_color(theme.prev_area, Vec2::ONE),
         Transform::from_xyz(0., 0., 2.),
         Visibility::Hidden,
         PrevRectangle,
@@ -177,6 +228,95 @@ fn board_cleanup(
     }
 }
 
+// fades the tiles in row by row from the bottom as the countdown runs
+fn reveal_board(
+    timer: Res<StartingTimer>,
+    tiles: Query<(Entity, &Tile, &Children)>,
+    mut sprites: Query<&mut Sprite>,
+    mut texts: Query<&mut TextColor>,
+) {
+    let reveal = timer.reveal();
+
+    for (entity, tile, children) in &tiles {
+        // each row starts a little after the one below and takes a third of the countdown
+        let start = tile.row as f32 / ROWS as f32 * (2. / 3.);
+        let alpha = ((reveal - start) * 3.).clamp(0., 1.);
+        set_tile_alpha(
+            children.iter().copied().chain([entity]),
+            &mut sprites,
+            &mut texts,
+            alpha,
+        );
+    }
+}
+
+fn finish_reveal(
+    tiles: Query<(Entity, &Children), With<Tile>>,
+    mut sprites: Query<&mut Sprite>,
+    mut texts: Query<&mut TextColor>,
+) {
+    for (entity, children) in &tiles {
+        set_tile_alpha(
+            children.iter().copied().chain([entity]),
+            &mut sprites,
+            &mut texts,
+            1.,
+        );
+    }
+}
+
+fn set_tile_alpha(
+    entities: impl Iterator<Item = Entity>,
+    sprites: &mut Query<&mut Sprite>,
+    texts: &mut Query<&mut TextColor>,
+    alpha: f32,
+) {
+    for entity in entities {
+        if let Ok(mut sprite) = sprites.get_mut(entity) {
+            sprite.color.set_alpha(alpha);
+        }
+        if let Ok(mut text) = texts.get_mut(entity) {
+            text.0.set_alpha(alpha);
+        }
+    }
+}
+
+fn hide_board(
+    mut tiles: Query<&mut Visibility, (With<Tile>, Without<Rectangle>)>,
+    mut rectangle: Query<&mut Visibility, With<Rectangle>>,
+) {
+    for mut visibility in &mut tiles {
+        *visibility = Visibility::Hidden;
+    }
+
+    for mut visibility in &mut rectangle {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn show_board(mut tiles: Query<&mut Visibility, With<Tile>>) {
+    for mut visibility in &mut tiles {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// picks up a newly chosen or edited theme mid game
+fn restyle_board(
+    theme: Res<ActiveTheme>,
+    mut sprites: Query<
+        (&mut Sprite, Has<Tile>, Has<Rectangle>),
+        Or<(With<Tile>, With<Rectangle>, With<PrevRectangle>)>,
+    >,
+) {
+    for (mut sprite, is_tile, is_rectangle) in &mut sprites {
+        sprite.color = match (is_tile, is_rectangle) {
+            (true, _) => theme.tile,
+            (_, true) => theme.selection_rectangle,
+            _ => theme.prev_area,
+        };
+    }
+}
+
 fn observe_poptiles_event(
     trigger: Trigger<TilesPoppedEvent>,
     // mut ev_writer: EventWriter<StartTileAnimationEvent>,
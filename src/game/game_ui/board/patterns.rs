@@ -0,0 +1,198 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+    sprite::SpriteImageMode,
+};
+
+use crate::{despawn_screen, game::GameState, menu::settings::GameConfig, theme::ActiveTheme};
+
+use super::{PrevRectangle, Rectangle, Tile};
+
+// shape cues so selections and the previous area can be told apart without relying on colour
+pub fn patterns_plugin(app: &mut App) {
+    app.add_systems(Startup, create_patterns)
+        .add_systems(OnEnter(GameState::Playing), spawn_patterns)
+        .add_systems(
+            OnExit(GameState::Playing),
+            (
+                despawn_screen::<SelectionPattern>,
+                despawn_screen::<PrevAreaPattern>,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                outline_selected_tiles,
+                follow_selection_rectangle,
+                follow_prev_area,
+                restyle_patterns.run_if(resource_changed::<ActiveTheme>),
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+}
+
+// pixels per pattern repeat
+const PATTERN_SIZE: u32 = 12;
+pub const OUTLINE_WIDTH: f32 = 3.;
+const PATTERN_ALPHA: f32 = 0.6;
+
+#[derive(Resource)]
+struct BoardPatterns {
+    hatch: Handle<Image>,
+    dots: Handle<Image>,
+}
+
+#[derive(Component)]
+pub struct TileOutline;
+
+#[derive(Component)]
+struct SelectionPattern;
+
+#[derive(Component)]
+struct PrevAreaPattern;
+
+fn create_patterns(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    commands.insert_resource(BoardPatterns {
+        hatch: images.add(pattern_image(|x, y| (x + y) % PATTERN_SIZE < 3)),
+        dots: images.add(pattern_image(|x, y| {
+            x % (PATTERN_SIZE / 2) < 2 && y % (PATTERN_SIZE / 2) < 2
+        })),
+    });
+}
+
+// white where `filled` is true and clear elsewhere, so sprites can tint it
+fn pattern_image(filled: impl Fn(u32, u32) -> bool) -> Image {
+    let data = (0..PATTERN_SIZE)
+        .flat_map(|y| (0..PATTERN_SIZE).map(move |x| (x, y)))
+        .flat_map(|(x, y)| match filled(x, y) {
+            true => [255, 255, 255, 255],
+            false => [255, 255, 255, 0],
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: PATTERN_SIZE,
+            height: PATTERN_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+fn pattern_sprite(image: &Handle<Image>, theme: &ActiveTheme) -> Sprite {
+    Sprite {
+        image: image.clone(),
+        color: theme.outline.with_alpha(PATTERN_ALPHA),
+        custom_size: Some(Vec2::ZERO),
+        image_mode: SpriteImageMode::Tiled {
+            tile_x: true,
+            tile_y: true,
+            stretch_value: 1.,
+        },
+        ..default()
+    }
+}
+
+fn spawn_patterns(mut commands: Commands, patterns: Res<BoardPatterns>, theme: Res<ActiveTheme>) {
+    commands.spawn((
+        pattern_sprite(&patterns.dots, &theme),
+        Visibility::Hidden,
+        SelectionPattern,
+    ));
+    commands.spawn((
+        pattern_sprite(&patterns.hatch, &theme),
+        Visibility::Hidden,
+        PrevAreaPattern,
+    ));
+}
+
+fn outline_selected_tiles(
+    config: Res<GameConfig>,
+    tiles: Query<(Ref<Tile>, &Children)>,
+    mut outlines: Query<&mut Visibility, With<TileOutline>>,
+) {
+    for (tile, children) in &tiles {
+        if !tile.is_changed() && !config.is_changed() {
+            continue;
+        }
+
+        let visibility = match tile.selected && config.shape_cues {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+
+        let mut outline_iter = outlines.iter_many_mut(children);
+        while let Some(mut outline) = outline_iter.fetch_next() {
+            *outline = visibility;
+        }
+    }
+}
+
+// the selection rectangle is anchored at its top right corner and scaled out to the cursor
+fn follow_selection_rectangle(
+    config: Res<GameConfig>,
+    rectangle: Query<(&Transform, &Visibility), (With<Rectangle>, Without<SelectionPattern>)>,
+    mut pattern: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<SelectionPattern>>,
+) {
+    let (Ok((rect_transform, rect_visibility)), Ok((mut sprite, mut transform, mut visibility))) =
+        (rectangle.get_single(), pattern.get_single_mut())
+    else {
+        return;
+    };
+
+    let size = rect_transform.scale.truncate();
+    sprite.custom_size = Some(size.abs());
+    transform.translation = (rect_transform.translation.truncate() - size / 2.)
+        .extend(rect_transform.translation.z + 0.1);
+    *visibility = match config.shape_cues {
+        true => *rect_visibility,
+        false => Visibility::Hidden,
+    };
+}
+
+fn follow_prev_area(
+    config: Res<GameConfig>,
+    prev_area: Query<(&Transform, &Visibility), (With<PrevRectangle>, Without<PrevAreaPattern>)>,
+    mut pattern: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<PrevAreaPattern>>,
+) {
+    let (Ok((area_transform, area_visibility)), Ok((mut sprite, mut transform, mut visibility))) =
+        (prev_area.get_single(), pattern.get_single_mut())
+    else {
+        return;
+    };
+
+    sprite.custom_size = Some(area_transform.scale.truncate());
+    transform.translation = area_transform.translation + Vec3::Z * 0.1;
+    *visibility = match config.shape_cues {
+        true => *area_visibility,
+        false => Visibility::Hidden,
+    };
+}
+
+fn restyle_patterns(
+    theme: Res<ActiveTheme>,
+    mut patterns: Query<&mut Sprite, Or<(With<SelectionPattern>, With<PrevAreaPattern>)>>,
+    mut outlines: Query<
+        &mut Sprite,
+        (
+            With<TileOutline>,
+            Without<SelectionPattern>,
+            Without<PrevAreaPattern>,
+        ),
+    >,
+) {
+    for mut sprite in &mut patterns {
+        sprite.color = theme.outline.with_alpha(PATTERN_ALPHA);
+    }
+
+    for mut sprite in &mut outlines {
+        sprite.color = theme.outline;
+    }
+}
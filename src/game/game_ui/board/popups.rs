@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    game::{playing::TilesPoppedEvent, GameState, InternalGameState, PauseState},
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+};
+
+use super::conversions::area_to_transform;
+
+// floating "+N" over each popped area, with a banner on top while a combo is going
+pub fn popups_plugin(app: &mut App) {
+    app.add_systems(OnExit(GameState::Playing), despawn_screen::<ScorePopup>)
+        .add_systems(Update, animate_popups.run_if(in_state(PauseState::Running)))
+        .add_observer(show_score_popup);
+}
+
+const POPUP_SECS: f32 = 1.2;
+const POPUP_RISE: f32 = 60.;
+// the banner starts at this size and grows with every link in the combo, up to the cap
+const BANNER_FONT_SIZE: f32 = 28.;
+const BANNER_GROWTH: f32 = 6.;
+const BANNER_MAX_FONT_SIZE: f32 = 64.;
+// above the score popup so the two don't overlap
+const BANNER_OFFSET: f32 = 50.;
+
+#[derive(Component)]
+struct ScorePopup {
+    timer: Timer,
+    y_start: f32,
+    color: Color,
+}
+
+fn show_score_popup(
+    _trigger: Trigger<TilesPoppedEvent>,
+    mut commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
+) {
+    let game = &internal_game_state.0;
+    let centre = area_to_transform(&game.prev_area, &config).translation;
+    let last_move = &game.last_move;
+
+    let breakdown = match last_move.combo {
+        0 => format!("{} tiles + {} area", last_move.tiles, last_move.area),
+        combo => format!(
+            "{} tiles + {} area + {} combo",
+            last_move.tiles, last_move.area, combo
+        ),
+    };
+
+    commands
+        .spawn((
+            ScorePopup {
+                timer: Timer::from_seconds(POPUP_SECS, TimerMode::Once),
+                y_start: centre.y,
+                color: theme.text,
+            },
+            Text2d::new(format!("+{}", last_move.total())),
+            TextFont::from_font_size(32.),
+            TextColor(theme.text),
+            Transform::from_xyz(centre.x, centre.y, 5.),
+        ))
+        .with_child((
+            Text2d::new(breakdown),
+            TextFont::from_font_size(14.),
+            TextColor(theme.muted_text),
+            Transform::from_xyz(0., -26., 0.),
+        ));
+
+    if game.combo > 0 {
+        let font_size =
+            (BANNER_FONT_SIZE + BANNER_GROWTH * game.combo as f32).min(BANNER_MAX_FONT_SIZE);
+        let y_start = centre.y + BANNER_OFFSET;
+
+        commands.spawn((
+            ScorePopup {
+                timer: Timer::from_seconds(POPUP_SECS, TimerMode::Once),
+                y_start,
+                color: theme.accent,
+            },
+            Text2d::new(format!("COMBO x{}!", game.combo)),
+            TextFont::from_font_size(font_size),
+            TextColor(theme.accent),
+            Transform::from_xyz(centre.x, y_start, 5.1),
+        ));
+    }
+}
+
+fn animate_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut popups: Query<(
+        Entity,
+        &mut ScorePopup,
+        &mut Transform,
+        &mut TextColor,
+        Option<&Children>,
+    )>,
+    mut breakdowns: Query<&mut TextColor, Without<ScorePopup>>,
+) {
+    for (entity, mut popup, mut transform, mut color, children) in &mut popups {
+        if popup.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // ease out so the number pops up quickly and then hangs while it fades
+        let progress = popup.timer.fraction();
+        let alpha = 1. - progress * progress;
+        if !config.reduced_motion {
+            transform.translation.y = popup.y_start + (1. - (1. - progress).powi(2)) * POPUP_RISE;
+        }
+        color.0 = popup.color.with_alpha(alpha);
+
+        let mut child_iter = breakdowns.iter_many_mut(children.into_iter().flatten());
+        while let Some(mut child_color) = child_iter.fetch_next() {
+            child_color.0.set_alpha(alpha);
+        }
+    }
+}
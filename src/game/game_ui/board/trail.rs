@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    game::{playing::TilesPoppedEvent, GameState, InternalGameState, PauseState},
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+};
+
+use super::conversions::area_to_transform;
+
+// the areas popped earlier in the current chain, fading the further back they are
+pub fn trail_plugin(app: &mut App) {
+    app.add_systems(OnExit(GameState::Playing), despawn_screen::<ChainLink>)
+        .add_systems(Update, fade_trail.run_if(in_state(PauseState::Running)))
+        .add_observer(extend_trail);
+}
+
+// how many earlier areas stay on the board
+const TRAIL_LENGTH: u32 = 5;
+// alpha of the most recent link, older ones fade towards nothing
+const TRAIL_ALPHA: f32 = 0.3;
+// once the chain breaks the whole trail fades out over this long
+const TRAIL_FADE_SECS: f32 = 0.5;
+
+#[derive(Component)]
+struct ChainLink {
+    age: u32, // pops since this area was popped, the newest is already drawn by PrevRectangle
+    fade: Option<Timer>,
+}
+
+fn extend_trail(
+    _trigger: Trigger<TilesPoppedEvent>,
+    mut commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
+    mut links: Query<(Entity, &mut ChainLink)>,
+) {
+    let game = &internal_game_state.0;
+    // a combo of zero means this pop didn't touch the last one, so it starts a new chain
+    let chain_broken = game.combo == 0;
+
+    for (entity, mut link) in &mut links {
+        if link.fade.is_some() {
+            continue;
+        }
+
+        link.age += 1;
+        if chain_broken {
+            link.fade = Some(Timer::from_seconds(TRAIL_FADE_SECS, TimerMode::Once));
+        } else if link.age > TRAIL_LENGTH {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let mut transform = area_to_transform(&game.prev_area, &config);
+    // under the tile numbers
+    transform.translation.z = 0.9;
+
+    commands.spawn((
+        ChainLink { age: 0, fade: None },
+        Sprite::from_color(theme.accent.with_alpha(0.), Vec2::ONE),
+        transform,
+    ));
+}
+
+fn fade_trail(
+    mut commands: Commands,
+    time: Res<Time>,
+    theme: Res<ActiveTheme>,
+    mut links: Query<(Entity, &mut ChainLink, &mut Sprite)>,
+) {
+    for (entity, mut link, mut sprite) in &mut links {
+        let mut alpha = match link.age {
+            0 => 0.,
+            age => {
+                TRAIL_ALPHA * (TRAIL_LENGTH + 1).saturating_sub(age) as f32 / TRAIL_LENGTH as f32
+            }
+        };
+
+        if let Some(fade) = link.fade.as_mut() {
+            if fade.tick(time.delta()).finished() {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+            alpha *= 1. - fade.fraction();
+        }
+
+        sprite.color = theme.accent.with_alpha(alpha);
+    }
+}
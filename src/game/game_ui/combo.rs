@@ -0,0 +1,113 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    despawn_screen,
+    game::{squaregg::ROWS, InternalGameState},
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+    SystemState,
+};
+
+// sits between the score and the timer, showing how long the chain is and what keeping it pays
+pub fn combo_plugin(app: &mut App) {
+    app.add_systems(OnEnter(SystemState::Game), combo_setup)
+        .add_systems(OnExit(SystemState::Game), despawn_screen::<OnComboMeter>)
+        .add_systems(Update, update_combo.run_if(in_state(SystemState::Game)));
+}
+
+const METER_SIZE: Vec2 = Vec2::new(160., 8.);
+// the combo bonus stops curving upwards here, so a full meter means the chain is at its best rate
+const METER_MAX_COMBO: i32 = 6;
+
+#[derive(Component)]
+struct OnComboMeter;
+
+#[derive(Component)]
+struct ComboText;
+
+#[derive(Component)]
+struct ComboMeterBack;
+
+#[derive(Component)]
+struct ComboMeterFill;
+
+fn combo_setup(mut commands: Commands, config: Res<GameConfig>, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((
+            OnComboMeter,
+            Transform::from_xyz(
+                0.,
+                (ROWS as f32 / 2.) * (config.tile_size + config.tile_gap) + 50.,
+                0.,
+            ),
+            Visibility::default(),
+        ))
+        .with_children(|meter| {
+            meter.spawn((
+                ComboText,
+                Text2d::default(),
+                TextFont::from_font_size(18.),
+                TextColor(theme.text),
+            ));
+
+            meter
+                .spawn((
+                    ComboMeterBack,
+                    Sprite {
+                        color: theme.panel,
+                        custom_size: Some(METER_SIZE),
+                        ..default()
+                    },
+                    Transform::from_xyz(0., -22., 0.),
+                ))
+                .with_child((
+                    ComboMeterFill,
+                    // sized by its transform scale
+                    Sprite {
+                        color: theme.accent,
+                        custom_size: Some(Vec2::ONE),
+                        anchor: Anchor::CenterLeft,
+                        ..default()
+                    },
+                    Transform::from_xyz(-METER_SIZE.x / 2., 0., 0.1).with_scale(Vec3::new(
+                        0.,
+                        METER_SIZE.y,
+                        1.,
+                    )),
+                ));
+        });
+}
+
+fn update_combo(
+    internal_game_state: Res<InternalGameState>,
+    theme: Res<ActiveTheme>,
+    mut text: Query<(&mut Text2d, &mut TextColor), With<ComboText>>,
+    mut back: Query<&mut Sprite, (With<ComboMeterBack>, Without<ComboMeterFill>)>,
+    mut fill: Query<(&mut Transform, &mut Sprite), With<ComboMeterFill>>,
+) {
+    let game = &internal_game_state.0;
+
+    if let Ok((mut text, mut color)) = text.get_single_mut() {
+        let label = match game.next_combo_bonus() {
+            Some(bonus) => format!("Combo x{}  next +{}", game.combo, bonus),
+            None => format!("Combo x{}", game.combo),
+        };
+        if text.0 != label {
+            text.0 = label;
+        }
+        color.0 = match game.combo {
+            0 => theme.muted_text,
+            _ => theme.text,
+        };
+    }
+
+    if let Ok(mut sprite) = back.get_single_mut() {
+        sprite.color = theme.panel;
+    }
+
+    if let Ok((mut transform, mut sprite)) = fill.get_single_mut() {
+        let filled = game.combo.min(METER_MAX_COMBO) as f32 / METER_MAX_COMBO as f32;
+        transform.scale.x = filled * METER_SIZE.x;
+        sprite.color = theme.accent;
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+
+use crate::{
+    game::squaregg::{COLS, ROWS},
+    menu::settings::GameConfig,
+    SystemState,
+};
+
+// zooms the camera so the board and its HUD fill the window, the board itself keeps its size in
+// world units so nothing that places tiles has to know about the window
+pub fn layout_plugin(app: &mut App) {
+    app.add_systems(OnEnter(SystemState::Game), fit_board_to_window)
+        .add_systems(OnExit(SystemState::Game), reset_camera_scale)
+        .add_systems(
+            Update,
+            fit_board_to_window.run_if(
+                in_state(SystemState::Game)
+                    .and(on_event::<WindowResized>.or(resource_changed::<GameConfig>)),
+            ),
+        );
+}
+
+// room left around the board for the score, timer and pause button, in world units
+const HUD_MARGIN: Vec2 = Vec2::new(180., 100.);
+
+fn fit_board_to_window(
+    config: Res<GameConfig>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut projection: Query<&mut OrthographicProjection, With<Camera2d>>,
+) {
+    let (Ok(window), Ok(mut projection)) = (windows.get_single(), projection.get_single_mut())
+    else {
+        return;
+    };
+
+    // minimised windows report a zero size
+    let window_size = window.size();
+    if window_size.min_element() <= 0. {
+        return;
+    }
+
+    let needed = Vec2::new(COLS as f32, ROWS as f32) * (config.tile_size + config.tile_gap)
+        + 2. * HUD_MARGIN;
+
+    // one scale for both axes keeps the tiles square, whichever axis is tighter decides it
+    projection.scale = (needed / window_size).max_element();
+}
+
+fn reset_camera_scale(mut projection: Query<&mut OrthographicProjection, With<Camera2d>>) {
+    for mut projection in &mut projection {
+        projection.scale = 1.;
+    }
+}
@@ -5,6 +5,8 @@ use crate::{despawn_screen, SystemState};
 use super::InternalGameState;
 
 mod board;
+mod combo;
+mod layout;
 mod options;
 mod score;
 mod timer;
@@ -15,7 +17,9 @@ mod timer;
 pub fn game_ui_plugin(app: &mut App) {
     app.add_plugins((
         board::board_plugin,
+        layout::layout_plugin,
         score::score_plugin,
+        combo::combo_plugin,
         timer::timer_plugin,
         options::options_plugin,
     ));
@@ -1,5 +1,350 @@
 use bevy::prelude::*;
 
+use super::board::BoardInput;
+use crate::{
+    actions::{action_just_pressed, Action, ActionState},
+    despawn_screen,
+    game::{finished::button_system, GameState, PauseState},
+    menu::{
+        settings::{in_game_settings, save_config, GameConfig, SettingKind},
+        spawn_button,
+        widgets::{clear_focused_setting, spawn_settings, FocusedSetting},
+        MenuButtonAction,
+    },
+    theme::ActiveTheme,
+    SystemState,
+};
+
 pub fn options_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Playing), pause_hud_setup)
+        .add_systems(OnExit(GameState::Playing), despawn_screen::<OnPauseHud>)
+        .add_systems(
+            OnEnter(PauseState::Paused),
+            (pause_menu_setup, select_first_option),
+        )
+        .add_systems(OnExit(PauseState::Paused), despawn_screen::<OnPauseMenu>)
+        .add_systems(
+            OnEnter(PauseState::Settings),
+            (pause_settings_setup, select_first_option),
+        )
+        .add_systems(
+            OnExit(PauseState::Settings),
+            (
+                despawn_screen::<OnPauseSettings>,
+                save_config,
+                clear_focused_setting,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                // after the board so cancelling a keyboard selection doesn't also pause
+                toggle_pause
+                    .run_if(action_just_pressed(Action::Pause))
+                    .after(BoardInput),
+                pause_action,
+                button_system,
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (
+                hover_pause_option,
+                select_pause_option,
+                confirm_pause_option.run_if(action_just_pressed(Action::Confirm)),
+                focus_pause_setting.run_if(in_state(PauseState::Settings)),
+                highlight_pause_option,
+            )
+                .chain()
+                .after(button_system)
+                .run_if(in_state(PauseState::Paused).or(in_state(PauseState::Settings))),
+        );
+}
+
+#[derive(Component)]
+struct OnPauseHud;
+
+#[derive(Component)]
+struct OnPauseMenu;
+
+#[derive(Component)]
+struct OnPauseSettings;
+
+// All actions that can be triggered from a button click
+#[derive(Component, Clone, Copy, PartialEq)]
+enum PauseButtonAction {
+    Pause,
+    Resume,
+    Restart,
+    Settings,
+    Back,
+    Quit,
+}
+
+// what up and down move between while paused, there's no egg selector in game so the selected
+// option is lit up instead
+#[derive(Resource, Clone, Copy, PartialEq)]
+enum PauseOption {
+    Button(PauseButtonAction),
+    Setting(SettingKind),
+}
+
+fn pause_options(pause_state: &PauseState) -> Vec<PauseOption> {
+    match pause_state {
+        PauseState::Running => vec![],
+        PauseState::Paused => [
+            PauseButtonAction::Resume,
+            PauseButtonAction::Restart,
+            PauseButtonAction::Settings,
+            PauseButtonAction::Quit,
+        ]
+        .into_iter()
+        .map(PauseOption::Button)
+        .collect(),
+        PauseState::Settings => std::iter::once(PauseOption::Button(PauseButtonAction::Back))
+            .chain(in_game_settings().into_iter().map(PauseOption::Setting))
+            .collect(),
+    }
+}
+
+fn pause_hud_setup(mut commands: Commands) {
+    commands
+        .spawn((
+            OnPauseHud,
+            PauseButtonAction::Pause,
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(20.),
+                right: Val::Px(20.),
+                width: Val::Px(60.),
+                height: Val::Px(60.),
+                border: UiRect::all(Val::Px(5.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BorderColor(Color::BLACK),
+            BorderRadius::all(Val::Px(10.)),
+        ))
+        .with_child(Text::new("II"));
+}
+
+fn pause_menu_setup(mut commands: Commands, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((OnPauseMenu, background(&theme)))
+        .with_children(|parent| {
+            parent.spawn(Text::new("Paused"));
+            spawn_button(parent, PauseButtonAction::Resume, "Resume");
+            spawn_button(parent, PauseButtonAction::Restart, "Restart");
+            spawn_button(parent, PauseButtonAction::Settings, "Settings");
+            spawn_button(parent, PauseButtonAction::Quit, "Quit to Menu");
+        });
+}
+
+fn pause_settings_setup(mut commands: Commands, config: Res<GameConfig>, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((OnPauseSettings, background(&theme)))
+        .with_children(|parent| {
+            spawn_button(parent, PauseButtonAction::Back, "Back");
+            spawn_settings(parent, &in_game_settings(), &config, &theme);
+        });
+}
+
+// opaque so the board can't be studied while the clock is stopped
+fn background(theme: &ActiveTheme) -> (Node, ZIndex, BackgroundColor) {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.),
+            height: Val::Percent(100.),
+            width: Val::Percent(100.),
+
+            ..Default::default()
+        },
+        ZIndex(60),
+        BackgroundColor(theme.overlay),
+    )
+}
+
+fn toggle_pause(
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    next_pause_state.set(match pause_state.get() {
+        PauseState::Running => PauseState::Paused,
+        PauseState::Paused => PauseState::Running,
+        PauseState::Settings => PauseState::Paused,
+    });
+}
+
+fn pause_action(
+    interaction_query: Query<
+        (&Interaction, &PauseButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut system_state: ResMut<NextState<SystemState>>,
+) {
+    for (interaction, pause_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            apply_pause_action(
+                pause_button_action,
+                &mut pause_state,
+                &mut game_state,
+                &mut system_state,
+            );
+        }
+    }
+}
+
+fn apply_pause_action(
+    pause_button_action: &PauseButtonAction,
+    pause_state: &mut NextState<PauseState>,
+    game_state: &mut NextState<GameState>,
+    system_state: &mut NextState<SystemState>,
+) {
+    match pause_button_action {
+        PauseButtonAction::Pause | PauseButtonAction::Back => {
+            pause_state.set(PauseState::Paused);
+        }
+        PauseButtonAction::Resume => {
+            pause_state.set(PauseState::Running);
+        }
+        PauseButtonAction::Restart => {
+            game_state.set(GameState::Starting);
+        }
+        PauseButtonAction::Settings => {
+            pause_state.set(PauseState::Settings);
+        }
+        PauseButtonAction::Quit => {
+            system_state.set(SystemState::Menu);
+        }
+    }
+}
+
+fn select_first_option(mut commands: Commands, pause_state: Res<State<PauseState>>) {
+    if let Some(first) = pause_options(pause_state.get()).first() {
+        commands.insert_resource(*first);
+    }
+}
+
+// the mouse moves the selection too, so keyboard and mouse never light up two options at once
+fn hover_pause_option(
+    buttons: Query<
+        (
+            &Interaction,
+            Option<&PauseButtonAction>,
+            Option<&MenuButtonAction>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    pause_state: Res<State<PauseState>>,
+    mut selection: ResMut<PauseOption>,
+) {
+    let options = pause_options(pause_state.get());
+
+    for (interaction, pause_action, menu_action) in &buttons {
+        if *interaction != Interaction::Hovered {
+            continue;
+        }
+
+        if let Some(option) = as_pause_option(pause_action, menu_action) {
+            if options.contains(&option) {
+                *selection = option;
+            }
+        }
+    }
+}
+
+fn as_pause_option(
+    pause_action: Option<&PauseButtonAction>,
+    menu_action: Option<&MenuButtonAction>,
+) -> Option<PauseOption> {
+    match (pause_action, menu_action) {
+        (Some(action), _) => Some(PauseOption::Button(*action)),
+        (_, Some(MenuButtonAction::Setting(kind))) => Some(PauseOption::Setting(*kind)),
+        _ => None,
+    }
+}
+
+fn select_pause_option(
+    actions: Res<ActionState>,
+    pause_state: Res<State<PauseState>>,
+    mut selection: ResMut<PauseOption>,
+) {
+    let options = pause_options(pause_state.get());
+    let Some(index) = options.iter().position(|option| *option == *selection) else {
+        return;
+    };
+
+    if actions.just_pressed(Action::Up) {
+        *selection = options[(index + options.len() - 1) % options.len()];
+    }
+
+    if actions.just_pressed(Action::Down) {
+        *selection = options[(index + 1) % options.len()];
+    }
+}
+
+// settings rows are changed with left and right instead, toggles are flipped by confirm there
+fn confirm_pause_option(
+    selection: Res<PauseOption>,
+    mut pause_state: ResMut<NextState<PauseState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut system_state: ResMut<NextState<SystemState>>,
+) {
+    if let PauseOption::Button(action) = *selection {
+        apply_pause_action(
+            &action,
+            &mut pause_state,
+            &mut game_state,
+            &mut system_state,
+        );
+    }
+}
+
+// left and right then adjust it the same way as on the menu's settings screen
+fn focus_pause_setting(selection: Res<PauseOption>, mut focused: ResMut<FocusedSetting>) {
+    let kind = match *selection {
+        PauseOption::Setting(kind) => Some(kind),
+        PauseOption::Button(_) => None,
+    };
+
+    focused.set_if_neq(FocusedSetting(kind));
+}
+
+fn highlight_pause_option(
+    selection: Res<PauseOption>,
+    pause_state: Res<State<PauseState>>,
+    theme: Res<ActiveTheme>,
+    mut buttons: Query<
+        (
+            &mut BackgroundColor,
+            Option<&PauseButtonAction>,
+            Option<&MenuButtonAction>,
+        ),
+        With<Button>,
+    >,
+) {
+    let options = pause_options(pause_state.get());
+
+    for (mut background, pause_action, menu_action) in &mut buttons {
+        // the pause button in the corner isn't one of the options
+        let Some(option) =
+            as_pause_option(pause_action, menu_action).filter(|option| options.contains(option))
+        else {
+            continue;
+        };
 
-}
\ No newline at end of file
+        background.0 = match option == *selection {
+            true => theme.button_hovered,
+            false => theme.button,
+        };
+    }
+}
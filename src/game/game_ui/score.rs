@@ -3,9 +3,8 @@ use bevy::prelude::*;
 use crate::{
     despawn_screen,
     game::{
-        playing::TilesPoppedEvent,
         squaregg::{COLS, ROWS},
-        InternalGameState,
+        GameState, InternalGameState,
     },
     menu::settings::GameConfig,
     SystemState,
@@ -15,15 +14,25 @@ pub fn score_plugin(app: &mut App) {
     app.add_systems(OnEnter(SystemState::Game), score_setup)
         // .add_systems(Update, update_score.run_if(in_state(GameState::Playing)));
         .add_systems(OnExit(SystemState::Game), despawn_screen::<OnScoreBoard>)
-        .add_observer(update_score);
+        // retrying or restarting keeps the score board around so zero it again
+        .add_systems(OnEnter(GameState::Starting), reset_score)
+        .add_systems(Update, update_score.run_if(in_state(SystemState::Game)));
         // .add_systems(Update, update_score);
 }
 
 #[derive(Component)]
 struct OnScoreBoard;
 
-#[derive(Component)]
-struct ScoreText;
+// the counter closes this fraction of the gap to the real score each second, but never slower
+// than SCORE_TICK_MIN points a second so the last few points don't crawl in
+const SCORE_TICK_RATE: f32 = 6.;
+const SCORE_TICK_MIN: f32 = 20.;
+
+// the score as currently shown, which lags behind the real score while it ticks up
+#[derive(Component, Default)]
+struct ScoreText {
+    shown: f32,
+}
 
 fn score_setup(mut commands: Commands, config: Res<GameConfig>) {
     commands
@@ -64,32 +73,39 @@ fn score_setup(mut commands: Commands, config: Res<GameConfig>) {
             //     ..Default::default()
             // },
         ))
-        .with_child((ScoreText, TextSpan::new("0")));
+        .with_child((ScoreText::default(), TextSpan::new("0")));
 }
 
 fn update_score(
-    // mut ev_reader: EventReader<TilesPoppedEvent>,
-    trigger: Trigger<TilesPoppedEvent>,
+    time: Res<Time>,
     internal_game_state: Res<InternalGameState>,
-    // mut score_board: Query<&mut Text, With<ScoreText>>,
-    score_board: Query<Entity, With<ScoreText>>,
+    mut score_board: Query<(Entity, &mut ScoreText)>,
     mut text_writer: Text2dWriter,
 ) {
-    // for _ in ev_reader.read() {
-    //     if let Ok(text_entity) = score_board.get_single() {
-    //         // text.sections[1].value = internal_game_state.0.score.to_string();
-    //         *text_writer.text(text_entity, 0) = internal_game_state.0.score.to_string();
-    //     }
-    
-    //     // println!("score: {:?}", internal_game_state.0.score);
-    // }
+    let target = internal_game_state.0.score as f32;
+
+    if let Ok((text_entity, mut score_text)) = score_board.get_single_mut() {
+        if score_text.shown == target {
+            return;
+        }
 
-    if let Ok(text_entity) = score_board.get_single() {
-        // text.sections[1].value = internal_game_state.0.score.to_string();
-        *text_writer.text(text_entity, 0) = internal_game_state.0.score.to_string();
+        let gap = target - score_text.shown;
+        let step = (gap.abs() * SCORE_TICK_RATE).max(SCORE_TICK_MIN) * time.delta_secs();
+        score_text.shown = match step >= gap.abs() {
+            true => target,
+            false => score_text.shown + step * gap.signum(),
+        };
+
+        *text_writer.text(text_entity, 0) = (score_text.shown as i32).to_string();
     }
 }
 
+fn reset_score(mut score_board: Query<(Entity, &mut ScoreText)>, mut text_writer: Text2dWriter) {
+    if let Ok((text_entity, mut score_text)) = score_board.get_single_mut() {
+        score_text.shown = 0.;
+        *text_writer.text(text_entity, 0) = "0".to_string();
+    }
+}
 
 // fn update_score(
 //     trigger: Trigger<TilesPoppedEvent>,
@@ -1,5 +1,180 @@
 use bevy::prelude::*;
 
+use crate::{
+    audio::Sfx,
+    despawn_screen,
+    game::{
+        playing::{GameFinishedEvent, TilesPoppedEvent},
+        squaregg::{COLS, ROWS},
+        GameState, InternalGameState, PauseState,
+    },
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+    SystemState,
+};
+
 pub fn timer_plugin(app: &mut App) {
+    app.add_systems(OnEnter(SystemState::Game), timer_setup)
+        .add_systems(
+            OnExit(SystemState::Game),
+            (
+                despawn_screen::<OnTimerBoard>,
+                despawn_screen::<TimeBonusPopup>,
+            ),
+        )
+        .add_systems(
+            Update,
+            (tick_timer, update_timer, animate_time_bonus)
+                .chain()
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_observer(show_time_bonus);
+}
+
+// seconds left on the clock before the timer text starts warning the player
+const WARNING_SECS: f32 = 10.;
+
+// how long the "+Ns" feedback floats above the timer and how far it rises
+const TIME_BONUS_POPUP_SECS: f32 = 1.;
+const TIME_BONUS_POPUP_RISE: f32 = 40.;
+
+#[derive(Component)]
+struct OnTimerBoard;
+
+#[derive(Component)]
+struct TimerText;
+
+#[derive(Component)]
+struct TimeBonusPopup {
+    timer: Timer,
+    y_start: f32,
+}
+
+fn timer_setup(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    internal_game_state: Res<InternalGameState>,
+    theme: Res<ActiveTheme>,
+) {
+    commands
+        .spawn((
+            OnTimerBoard,
+            Text2d::new("Time: "),
+            TextColor(theme.text),
+            Transform::from_xyz(
+                (COLS as f32 / 2.) * (config.tile_size + config.tile_gap),
+                (ROWS as f32 / 2.) * (config.tile_size + config.tile_gap) + 50.,
+                0.,
+            ),
+        ))
+        .with_child((
+            TimerText,
+            TextSpan::new(format_time(internal_game_state.0.timer.remaining_secs())),
+        ));
+}
+
+// the game clock only runs while playing, so the starting countdown doesn't eat into it
+fn tick_timer(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut internal_game_state: ResMut<InternalGameState>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if internal_game_state.0.tick(time.delta()) {
+        commands.trigger(GameFinishedEvent);
+        game_state.set(GameState::Finished);
+    }
+}
+
+fn update_timer(
+    internal_game_state: Res<InternalGameState>,
+    mut timer_board: Query<(Entity, &mut Transform), With<OnTimerBoard>>,
+    timer_text: Query<Entity, With<TimerText>>,
+    mut text_writer: Text2dWriter,
+    theme: Res<ActiveTheme>,
+    config: Res<GameConfig>,
+    mut last_second: Local<u32>,
+    mut sfx: EventWriter<Sfx>,
+) {
+    let remaining = internal_game_state.0.timer.remaining_secs();
+
+    // tick each time the clock shows a new second in the warning stretch
+    let second = remaining.ceil() as u32;
+    if second != *last_second && remaining <= WARNING_SECS && remaining > 0. {
+        sfx.send(Sfx::Tick);
+    }
+    *last_second = second;
+
+    if let Ok(text_entity) = timer_text.get_single() {
+        *text_writer.text(text_entity, 0) = format_time(remaining);
+    }
+
+    if let Ok((board_entity, mut transform)) = timer_board.get_single_mut() {
+        let (pulse, color) = match remaining <= WARNING_SECS {
+            // pulse once per second, peaking as each second ticks over
+            true => (0.25 * remaining.fract(), theme.warning),
+            false => (0., theme.text),
+        };
+
+        // the colour still warns with reduced motion, it just doesn't pulse
+        transform.scale = match config.reduced_motion {
+            true => Vec3::ONE,
+            false => Vec3::splat(1. + pulse),
+        };
+        // spans don't inherit the root colour so set both the label and the time
+        *text_writer.color(board_entity, 0) = TextColor(color);
+        *text_writer.color(board_entity, 1) = TextColor(color);
+    }
+}
+
+// remaining time as m:ss, rounded up so the clock reads 0:00 only once time is up
+fn format_time(remaining_secs: f32) -> String {
+    let secs = remaining_secs.ceil() as u32;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn show_time_bonus(
+    _trigger: Trigger<TilesPoppedEvent>,
+    mut commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    timer_board: Query<&Transform, With<OnTimerBoard>>,
+    theme: Res<ActiveTheme>,
+) {
+    let time_bonus = internal_game_state.0.time_bonus;
+
+    if time_bonus <= 0. {
+        return;
+    }
+
+    if let Ok(transform) = timer_board.get_single() {
+        let y_start = transform.translation.y + 30.;
+
+        commands.spawn((
+            TimeBonusPopup {
+                timer: Timer::from_seconds(TIME_BONUS_POPUP_SECS, TimerMode::Once),
+                y_start,
+            },
+            Text2d::new(format!("+{}s", time_bonus.round())),
+            TextColor(theme.accent),
+            Transform::from_xyz(transform.translation.x, y_start, 0.),
+        ));
+    }
+}
+
+fn animate_time_bonus(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut popups: Query<(Entity, &mut TimeBonusPopup, &mut Transform, &mut TextColor)>,
+    theme: Res<ActiveTheme>,
+) {
+    for (entity, mut popup, mut transform, mut color) in &mut popups {
+        if popup.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
 
-}
\ No newline at end of file
+        let progress = popup.timer.fraction();
+        transform.translation.y = popup.y_start + progress * TIME_BONUS_POPUP_RISE;
+        color.0 = theme.accent.with_alpha(1. - progress);
+    }
+}
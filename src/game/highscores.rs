@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::{Action, ActionState},
+    storage::{data_dir, load_ron, save_ron},
+    theme::Theme,
+};
+
+use super::{
+    squaregg::{Squaregg, COLS, ROWS, RULESET},
+    GameState,
+};
+
+pub fn highscores_plugin(app: &mut App) {
+    app.add_systems(Startup, load_high_scores)
+        .add_systems(
+            Update,
+            enter_name
+                .run_if(in_state(GameState::Finished).and(resource_exists::<PendingHighScore>)),
+        )
+        .add_systems(OnExit(GameState::Finished), save_pending_high_score);
+}
+
+// how many entries each table keeps
+const TABLE_SIZE: usize = 10;
+const MAX_NAME_LEN: usize = 12;
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct HighScores {
+    pub last_name: String, // prefilled the next time a player places
+    pub tables: BTreeMap<String, Vec<HighScoreEntry>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub date: String,
+    pub seed: u64,
+    pub score: i32,
+}
+
+// the entry the player is still naming on the results screen
+#[derive(Resource)]
+pub struct PendingHighScore {
+    pub key: String,
+    pub index: usize,
+    // the entry starts with the last name used, the first key typed replaces it rather than
+    // adding to it
+    pub edited: bool,
+}
+
+#[derive(Component)]
+struct PendingNameText;
+
+impl HighScores {
+    fn path() -> PathBuf {
+        data_dir().join("highscores.ron")
+    }
+
+    pub fn player_name(&self) -> String {
+        match self.last_name.is_empty() {
+            true => "Player".to_string(),
+            false => self.last_name.clone(),
+        }
+    }
+
+    pub fn save(&self) {
+        if let Err(err) = save_ron(&Self::path(), self) {
+            warn!("couldn't save high scores: {}", err);
+        }
+    }
+
+    // places the entry in its table, returning where it landed if it made the cut
+    pub fn insert(&mut self, key: String, entry: HighScoreEntry) -> Option<usize> {
+        let table = self.tables.entry(key).or_default();
+        let index = table.partition_point(|placed| placed.score >= entry.score);
+
+        if index >= TABLE_SIZE {
+            return None;
+        }
+
+        table.insert(index, entry);
+        table.truncate(TABLE_SIZE);
+        Some(index)
+    }
+}
+
+// scores are only comparable between games with the same mode, board and rules
+pub fn table_key(game: &Squaregg) -> String {
+    format!("{} {}x{} {}", game.mode, COLS, ROWS, RULESET)
+}
+
+fn load_high_scores(mut commands: Commands) {
+    commands.insert_resource(load_ron::<HighScores>(&HighScores::path()));
+}
+
+pub fn spawn_high_score_table(
+    parent: &mut ChildBuilder<'_>,
+    title: &str,
+    table: &[HighScoreEntry],
+    highlight: Option<usize>,
+    theme: &Theme,
+) {
+    parent
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Start,
+            row_gap: Val::Px(4.),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(Text::new(title));
+
+            if table.is_empty() {
+                parent.spawn(Text::new("No scores yet"));
+            }
+
+            table.iter().enumerate().for_each(|(index, entry)| {
+                let color = match highlight == Some(index) {
+                    true => theme.accent,
+                    false => theme.text,
+                };
+
+                parent
+                    .spawn((Text::new(format!("{:>2}. ", index + 1)), TextColor(color)))
+                    .with_children(|row| {
+                        let mut name = row.spawn((TextSpan::new(&entry.name), TextColor(color)));
+                        if highlight == Some(index) {
+                            name.insert(PendingNameText);
+                        }
+
+                        row.spawn((
+                            TextSpan::new(format!(
+                                "  {}  {}  seed {}",
+                                entry.score, entry.date, entry.seed
+                            )),
+                            TextColor(color),
+                        ));
+                    });
+            });
+        });
+}
+
+// lets the player type a name for their new entry straight onto the table
+fn enter_name(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut high_scores: ResMut<HighScores>,
+    mut pending: ResMut<PendingHighScore>,
+    mut name_text: Query<&mut TextSpan, With<PendingNameText>>,
+    mut actions: ResMut<ActionState>,
+) {
+    let fallback = high_scores.player_name();
+    let Some(entry) = high_scores
+        .tables
+        .get_mut(&pending.key)
+        .and_then(|table| table.get_mut(pending.index))
+    else {
+        return;
+    };
+
+    let mut submitted = false;
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        let typing = matches!(event.logical_key, Key::Character(_) | Key::Space);
+        if typing && !pending.edited {
+            entry.name.clear();
+        }
+        pending.edited |= typing || event.logical_key == Key::Backspace;
+
+        match &event.logical_key {
+            Key::Enter => submitted = true,
+            Key::Backspace => {
+                entry.name.pop();
+            }
+            Key::Character(input) => {
+                input.chars().filter(|c| !c.is_control()).for_each(|c| {
+                    if entry.name.chars().count() < MAX_NAME_LEN {
+                        entry.name.push(c);
+                    }
+                });
+            }
+            Key::Space if entry.name.chars().count() < MAX_NAME_LEN => entry.name.push(' '),
+            _ => {}
+        }
+    }
+
+    if let Ok(mut text) = name_text.get_single_mut() {
+        text.0 = format!("{}_", entry.name);
+    }
+
+    if submitted {
+        // clearing the name entirely keeps the one that was there
+        if entry.name.trim().is_empty() {
+            entry.name = fallback;
+        }
+        let name = entry.name.clone();
+        if let Ok(mut text) = name_text.get_single_mut() {
+            text.0 = name.clone();
+        }
+
+        high_scores.last_name = name;
+        high_scores.save();
+        commands.remove_resource::<PendingHighScore>();
+        // the same enter press shouldn't also start another game
+        actions.clear_just_pressed(Action::Confirm);
+    }
+}
+
+// leaving the results screen keeps whatever name was typed so far
+fn save_pending_high_score(
+    mut commands: Commands,
+    mut high_scores: ResMut<HighScores>,
+    pending: Option<Res<PendingHighScore>>,
+) {
+    if let Some(pending) = pending {
+        if let Some(name) = high_scores
+            .tables
+            .get(&pending.key)
+            .and_then(|table| table.get(pending.index))
+            .map(|entry| entry.name.clone())
+        {
+            high_scores.last_name = name;
+        }
+
+        high_scores.save();
+        commands.remove_resource::<PendingHighScore>();
+    }
+}
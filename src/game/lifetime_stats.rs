@@ -0,0 +1,89 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{data_dir, load_ron, save_ron};
+
+use super::{GameState, InternalGameState};
+
+pub fn lifetime_stats_plugin(app: &mut App) {
+    app.add_systems(Startup, load_lifetime_stats)
+        .add_systems(OnEnter(GameState::Finished), record_game);
+}
+
+// width of each score histogram bucket
+pub const HISTOGRAM_BUCKET: i32 = 50;
+
+// aggregate stats across every finished game
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub games_played: u32,
+    pub total_tiles_popped: u32,
+    pub total_score: i64,
+    pub best_score: i32,
+    pub score_histogram: BTreeMap<i32, u32>, // bucket start to number of games
+    pub rect_sizes: BTreeMap<String, u32>,   // "widthxheight" to number of pops
+    pub tile_values: [u32; 9],               // how many of each value 1 to 9 were popped
+}
+
+impl LifetimeStats {
+    fn path() -> PathBuf {
+        data_dir().join("stats.ron")
+    }
+
+    pub fn average_score(&self) -> Option<f32> {
+        match self.games_played {
+            0 => None,
+            games => Some(self.total_score as f32 / games as f32),
+        }
+    }
+
+    // most popped rectangle sizes first
+    pub fn common_rect_sizes(&self, count: usize) -> Vec<(&String, &u32)> {
+        let mut sizes: Vec<_> = self.rect_sizes.iter().collect();
+        sizes.sort_by(|a, b| b.1.cmp(a.1));
+        sizes.truncate(count);
+        sizes
+    }
+}
+
+fn load_lifetime_stats(mut commands: Commands) {
+    commands.insert_resource(load_ron::<LifetimeStats>(&LifetimeStats::path()));
+}
+
+fn record_game(
+    internal_game_state: Res<InternalGameState>,
+    mut lifetime_stats: ResMut<LifetimeStats>,
+) {
+    let game = &internal_game_state.0;
+
+    lifetime_stats.games_played += 1;
+    lifetime_stats.total_tiles_popped += game.stats.tiles_cleared as u32;
+    lifetime_stats.total_score += game.score as i64;
+    lifetime_stats.best_score = lifetime_stats.best_score.max(game.score);
+
+    let bucket = game.score.div_euclid(HISTOGRAM_BUCKET) * HISTOGRAM_BUCKET;
+    *lifetime_stats.score_histogram.entry(bucket).or_default() += 1;
+
+    game.stats
+        .rect_sizes
+        .iter()
+        .for_each(|((width, height), count)| {
+            *lifetime_stats
+                .rect_sizes
+                .entry(format!("{}x{}", width, height))
+                .or_default() += *count as u32;
+        });
+
+    game.stats
+        .tile_values
+        .iter()
+        .enumerate()
+        .for_each(|(i, count)| lifetime_stats.tile_values[i] += *count as u32);
+
+    if let Err(err) = save_ron(&LifetimeStats::path(), &*lifetime_stats) {
+        warn!("couldn't save lifetime stats: {}", err);
+    }
+}
@@ -1,30 +1,45 @@
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 use squaregg::Squaregg;
 
-use crate::SystemState;
+use crate::{
+    actions::{action_just_pressed, Action},
+    SystemState,
+};
 
 mod squaregg; // internal game board state
 
 mod game_ui; // board with tiles - requires InternalGameState
 
+pub(crate) mod achievements;
 mod finished;
+pub(crate) mod highscores;
+pub(crate) mod lifetime_stats;
 mod playing;
+mod share;
 mod starting;
 
 pub fn game_plugin(app: &mut App) {
     app.init_state::<GameState>()
+        .add_sub_state::<PauseState>()
         .insert_resource(InternalGameState(Squaregg::new()))
         .add_plugins(game_ui::game_ui_plugin)
         .add_systems(OnEnter(SystemState::Game), game_setup)
+        .add_systems(OnEnter(GameState::Starting), reset_board)
         .add_systems(OnExit(SystemState::Game), game_cleanup)
         .add_plugins((
             starting::starting_plugin,
             playing::playing_plugin,
             finished::finished_plugin,
+            highscores::highscores_plugin,
+            lifetime_stats::lifetime_stats_plugin,
+            achievements::achievements_plugin,
         ))
         .add_systems(
             Update,
-            go_main_menu.run_if(input_just_pressed(KeyCode::Escape)),
+            // while playing, pause opens the pause menu instead
+            go_main_menu.run_if(
+                action_just_pressed(Action::Cancel).and(not(in_state(GameState::Playing))),
+            ),
         );
 }
 
@@ -37,15 +52,25 @@ enum GameState {
     Disabled,
 }
 
+// only exists while playing, so systems gated on `PauseState::Running` stop when paused
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, SubStates)]
+#[source(GameState = GameState::Playing)]
+enum PauseState {
+    #[default]
+    Running,
+    Paused,
+    Settings,
+}
+
 #[derive(Resource)]
 pub struct InternalGameState(Squaregg);
 
-fn game_setup(
-    commands: Commands,
-    mut menu_state: ResMut<NextState<GameState>>,
-    mut internal_game_state: ResMut<InternalGameState>,
-) {
+fn game_setup(mut menu_state: ResMut<NextState<GameState>>) {
     menu_state.set(GameState::Starting);
+}
+
+// reset on every start so retrying or restarting gets a fresh board
+fn reset_board(mut internal_game_state: ResMut<InternalGameState>) {
     internal_game_state.0.reset();
 }
 
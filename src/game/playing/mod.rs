@@ -18,6 +18,10 @@ pub struct TilesPoppedEvent {
     pub tiles: Vec<Position>
 }
 
+// triggered once the game ends, before the results screen is shown
+#[derive(Event)]
+pub struct GameFinishedEvent;
+
 fn game_playing_setup(commands: Commands) {
 
 }
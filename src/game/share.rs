@@ -0,0 +1,142 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use arboard::Clipboard;
+use bevy::{
+    image::Image,
+    prelude::Resource,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::storage::data_dir;
+
+use super::squaregg::Squaregg;
+
+// size in pixels of each board cell on the image card
+const CARD_CELL: u32 = 16;
+const CARD_GAP: u32 = 2;
+const CARD_MARGIN: u32 = 12;
+
+const CARD_BACKGROUND: [u8; 4] = [20, 40, 40, 255];
+const CARD_CLEARED: [u8; 4] = [50, 205, 180, 255];
+const CARD_REMAINING: [u8; 4] = [232, 232, 214, 255];
+
+// opened on the first share and kept for the rest of the session, on linux the copied text is
+// served by the game and vanishes once the last clipboard handle is dropped
+#[derive(Resource, Default)]
+pub struct SystemClipboard(Option<Clipboard>);
+
+pub struct ShareResult {
+    pub folder: PathBuf,
+    pub copied_to_clipboard: bool,
+}
+
+// writes a text and image card of the finished game to the shares folder
+pub fn share_results(game: &Squaregg, clipboard: &mut SystemClipboard) -> io::Result<ShareResult> {
+    let folder = data_dir().join("shares");
+    fs::create_dir_all(&folder)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+    let name = format!("squaregg-{}-{}", game.seed, timestamp);
+
+    let summary = text_summary(game);
+    fs::write(folder.join(format!("{}.txt", name)), &summary)?;
+    save_image_card(game, &folder.join(format!("{}.png", name)))?;
+
+    Ok(ShareResult {
+        folder,
+        copied_to_clipboard: copy_to_clipboard(clipboard, summary),
+    })
+}
+
+fn text_summary(game: &Squaregg) -> String {
+    // the board is stored bottom row first so flip it to read top down
+    let mini_map = game
+        .board
+        .iter()
+        .rev()
+        .map(|row| {
+            row.iter()
+                .map(|tile| match tile {
+                    Some(_) => "⬜",
+                    None => "🟩",
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        "Squaregg {} - {} points\nSeed {}\n{}\n",
+        game.mode, game.score, game.seed, mini_map
+    )
+}
+
+fn save_image_card(game: &Squaregg, path: &Path) -> io::Result<()> {
+    let rows = game.board.len() as u32;
+    let cols = game.board.first().map_or(0, |row| row.len()) as u32;
+    let width = 2 * CARD_MARGIN + cols * (CARD_CELL + CARD_GAP) - CARD_GAP;
+    let height = 2 * CARD_MARGIN + rows * (CARD_CELL + CARD_GAP) - CARD_GAP;
+
+    let mut data: Vec<u8> = CARD_BACKGROUND
+        .iter()
+        .copied()
+        .cycle()
+        .take((width * height * 4) as usize)
+        .collect();
+
+    for (row_index, row) in game.board.iter().rev().enumerate() {
+        for (col_index, tile) in row.iter().enumerate() {
+            let color = match tile {
+                Some(_) => CARD_REMAINING,
+                None => CARD_CLEARED,
+            };
+            let x0 = CARD_MARGIN + col_index as u32 * (CARD_CELL + CARD_GAP);
+            let y0 = CARD_MARGIN + row_index as u32 * (CARD_CELL + CARD_GAP);
+
+            for y in y0..y0 + CARD_CELL {
+                for x in x0..x0 + CARD_CELL {
+                    let i = ((y * width + x) * 4) as usize;
+                    data[i..i + 4].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+    .try_into_dynamic()
+    .map_err(io::Error::other)?
+    .save(path)
+    .map_err(io::Error::other)
+}
+
+// best effort, a headless session or one without a clipboard manager has nowhere to copy to
+fn copy_to_clipboard(clipboard: &mut SystemClipboard, text: String) -> bool {
+    if clipboard.0.is_none() {
+        clipboard.0 = Clipboard::new().ok();
+    }
+
+    clipboard
+        .0
+        .as_mut()
+        .is_some_and(|clipboard| clipboard.set_text(text).is_ok())
+}
@@ -1,14 +1,38 @@
 use core::fmt;
+use std::{collections::BTreeMap, time::Duration};
 
 use bevy::prelude::*;
 
-use rand::{thread_rng, Rng};
+use rand::{random, rngs::StdRng, Rng, SeedableRng};
 
 use super::playing::TilesPoppedEvent;
 
 pub const ROWS: usize = 11;
 pub const COLS: usize = 18;
 const DURATION: f32 = 200.;
+// change whenever scoring or timing rules change so old high scores aren't mixed with new ones
+pub const RULESET: &str = "standard";
+
+// combo needed before a pop starts buying back time
+const COMBO_TIME_BONUS_MIN: i32 = 2;
+const MAX_COMBO_TIME_BONUS: i32 = 5;
+// areas larger than this many tiles earn a flat time bonus
+const AREA_TIME_BONUS_THRESHOLD: i32 = 12;
+const AREA_TIME_BONUS: f32 = 3.;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GameMode {
+    #[default]
+    Timed,
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Timed => write!(f, "Timed"),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Area {
@@ -30,6 +54,14 @@ impl Default for Area {
 }
 
 impl Area {
+    pub fn width(&self) -> i32 {
+        self.right - self.left + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.upper - self.lower + 1
+    }
+
     pub fn intersect(&self, other: &Area) -> bool {
         if self.right < other.left || other.right < self.left {
             return false;
@@ -41,6 +73,53 @@ impl Area {
 
         true
     }
+
+    pub fn contains(&self, row: i32, col: i32) -> bool {
+        (self.lower..=self.upper).contains(&row) && (self.left..=self.right).contains(&col)
+    }
+}
+
+// per game statistics shown on the results screen
+#[derive(Debug, Clone, Default)]
+pub struct GameStats {
+    pub tiles_cleared: i32,
+    pub moves: i32,
+    pub rejected: i32,
+    pub longest_combo: i32,
+    pub largest_area: i32,
+    pub score_from_tiles: i32,
+    pub score_from_area: i32,
+    pub score_from_combo: i32,
+    pub play_time: Duration,
+    pub rect_sizes: BTreeMap<(i32, i32), i32>, // (width, height) of each popped area
+    pub tile_values: [i32; 9],                 // how many of each value 1 to 9 were popped
+}
+
+// what the last pop scored, split the same way as the score formula
+#[derive(Debug, Clone, Default)]
+pub struct MoveScore {
+    pub tiles: i32,
+    pub area: i32,
+    pub combo: i32,
+}
+
+impl MoveScore {
+    pub fn total(&self) -> i32 {
+        self.tiles + self.area + self.combo
+    }
+}
+
+impl GameStats {
+    pub fn percent_cleared(&self) -> f32 {
+        100. * self.tiles_cleared as f32 / (ROWS * COLS) as f32
+    }
+
+    pub fn average_move_secs(&self) -> Option<f32> {
+        match self.moves {
+            0 => None,
+            moves => Some(self.play_time.as_secs_f32() / moves as f32),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -51,10 +130,15 @@ pub struct Position {
 
 pub struct Squaregg {
     pub board: Vec<Vec<Option<i32>>>,
+    pub seed: u64, // the same seed always deals the same board
+    pub mode: GameMode,
     pub score: i32,
     pub combo: i32,
     pub timer: Timer,
     pub prev_area: Area,
+    pub time_bonus: f32, // seconds added to the timer by the last pop
+    pub last_move: MoveScore,
+    pub stats: GameStats,
 }
 
 impl fmt::Debug for Squaregg {
@@ -86,36 +170,67 @@ impl fmt::Debug for Squaregg {
 
 impl Squaregg {
     pub fn new() -> Self {
-        let mut rng_generator = thread_rng();
+        let seed = random();
 
         Squaregg {
-            board: (0..ROWS)
-                .map(|_| {
-                    (0..COLS)
-                        .map(|_| Some(rng_generator.gen_range(1..10)))
-                        .collect()
-                })
-                .collect(),
+            board: generate_board(seed),
+            seed,
+            mode: GameMode::default(),
             score: 0,
             combo: 0,
-            timer: Timer::from_seconds(DURATION, TimerMode::Repeating),
+            timer: Timer::from_seconds(DURATION, TimerMode::Once),
             prev_area: Area::default(),
+            time_bonus: 0.,
+            last_move: MoveScore::default(),
+            stats: GameStats::default(),
         }
     }
 
     pub fn reset(&mut self) {
-        let mut rng_generator = thread_rng();
-
-        self.board = (0..ROWS)
-            .map(|_| {
-                (0..COLS)
-                    .map(|_| Some(rng_generator.gen_range(1..10)))
-                    .collect()
-            })
-            .collect();
+        self.seed = random();
+        self.board = generate_board(self.seed);
         self.score = 0;
-        self.timer = Timer::from_seconds(DURATION, TimerMode::Repeating);
+        self.combo = 0;
+        self.timer = Timer::from_seconds(DURATION, TimerMode::Once);
         self.prev_area = Area::default();
+        self.time_bonus = 0.;
+        self.last_move = MoveScore::default();
+        self.stats = GameStats::default();
+    }
+
+    // what the next pop would add for keeping the chain going, None until there is an area to
+    // chain from
+    pub fn next_combo_bonus(&self) -> Option<i32> {
+        match self.prev_area.upper < self.prev_area.lower {
+            true => None,
+            false => Some(combo_bonus(self.combo + 1)),
+        }
+    }
+
+    // advances the clock, returning true once time has run out
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.stats.play_time += delta;
+        self.timer.tick(delta).just_finished()
+    }
+
+    pub fn reject_move(&mut self) {
+        self.stats.rejected += 1;
+    }
+
+    // the sum of `tiles` and what popping them would score, whether or not they add up to 10
+    pub fn preview_move(&self, tiles: &[Position]) -> (i32, i32) {
+        if tiles.is_empty() {
+            return (0, 0);
+        }
+
+        let bounds = tile_bounds(tiles);
+        let sum = tiles
+            .iter()
+            .filter_map(|pos| self.board[pos.row][pos.col])
+            .sum();
+        let combo = combo_multiplier(&bounds, &self.prev_area, self.combo).unwrap_or(0);
+
+        (sum, tiles.len() as i32 + area_multiplier(&bounds) + combo)
     }
 
     // on success get the previous area
@@ -129,33 +244,18 @@ impl Squaregg {
         // mut ev: EventWriter<TilesPoppedEvent>,
         // mut commands: Commands
     ) -> bool {
-        let mut bounds = Area::default();
-
-        for pos in tiles {
-            if (pos.row as i32) < bounds.lower {
-                bounds.lower = pos.row as i32
-            }
-
-            if (pos.row as i32) > bounds.upper {
-                bounds.upper = pos.row as i32
-            }
-
-            if (pos.col as i32) < bounds.left {
-                bounds.left = pos.col as i32
-            }
-
-            if (pos.col as i32) > bounds.right {
-                bounds.right = pos.col as i32
-            }
-        }
+        let bounds = tile_bounds(tiles);
 
+        // an already popped cell can't be part of a move
         if let Some(sum) = tiles.iter().try_fold(0i32, |acc, tile| {
-            acc.checked_add(self.board[tile.row][tile.col].unwrap())
+            self.board[tile.row][tile.col].and_then(|val| acc.checked_add(val))
         }) {
             if sum == 10 {
                 // also some event writer or something
                 tiles.iter().for_each(|pos| {
-                    self.board[pos.row][pos.col] = None;
+                    if let Some(val) = self.board[pos.row][pos.col].take() {
+                        self.stats.tile_values[val as usize - 1] += 1;
+                    }
                 });
 
                 let combo_multiplier = match combo_multiplier(&bounds, &self.prev_area, self.combo)
@@ -170,7 +270,29 @@ impl Squaregg {
                     }
                 };
 
-                self.score += tiles.len() as i32 + area_multiplier(&bounds) + combo_multiplier;
+                let area_multiplier = area_multiplier(&bounds);
+                self.last_move = MoveScore {
+                    tiles: tiles.len() as i32,
+                    area: area_multiplier,
+                    combo: combo_multiplier,
+                };
+                self.score += self.last_move.total();
+
+                self.stats.tiles_cleared += tiles.len() as i32;
+                self.stats.moves += 1;
+                self.stats.longest_combo = self.stats.longest_combo.max(self.combo);
+                self.stats.largest_area = self.stats.largest_area.max(area_multiplier);
+                self.stats.score_from_tiles += tiles.len() as i32;
+                self.stats.score_from_area += area_multiplier;
+                self.stats.score_from_combo += combo_multiplier;
+                *self
+                    .stats
+                    .rect_sizes
+                    .entry((bounds.width(), bounds.height()))
+                    .or_default() += 1;
+
+                self.time_bonus = time_bonus(&bounds, self.combo);
+                self.add_time(self.time_bonus);
 
                 self.prev_area = bounds.clone();
 
@@ -178,12 +300,62 @@ impl Squaregg {
                 //     tiles: tiles.to_vec()
                 // });
 
-                return true
+                return true;
             }
         }
 
+        // clicking without covering any tiles isn't an attempt
+        if !tiles.is_empty() {
+            self.reject_move();
+        }
+
         false
     }
+
+    // winds the timer back, never past the full duration
+    fn add_time(&mut self, secs: f32) {
+        let elapsed = self
+            .timer
+            .elapsed()
+            .saturating_sub(Duration::from_secs_f32(secs));
+        self.timer.set_elapsed(elapsed);
+    }
+}
+
+fn generate_board(seed: u64) -> Vec<Vec<Option<i32>>> {
+    let mut rng_generator = StdRng::seed_from_u64(seed);
+
+    (0..ROWS)
+        .map(|_| {
+            (0..COLS)
+                .map(|_| Some(rng_generator.gen_range(1..10)))
+                .collect()
+        })
+        .collect()
+}
+
+fn tile_bounds(tiles: &[Position]) -> Area {
+    let mut bounds = Area::default();
+
+    for pos in tiles {
+        if (pos.row as i32) < bounds.lower {
+            bounds.lower = pos.row as i32
+        }
+
+        if (pos.row as i32) > bounds.upper {
+            bounds.upper = pos.row as i32
+        }
+
+        if (pos.col as i32) < bounds.left {
+            bounds.left = pos.col as i32
+        }
+
+        if (pos.col as i32) > bounds.right {
+            bounds.right = pos.col as i32
+        }
+    }
+
+    bounds
 }
 
 fn area_multiplier(area: &Area) -> i32 {
@@ -194,14 +366,28 @@ fn area_multiplier(area: &Area) -> i32 {
 
 fn combo_multiplier(area: &Area, prev_area: &Area, curr_combo: i32) -> Option<i32> {
     match area.intersect(prev_area) {
-        true => {
-            let n_combo = curr_combo + 1;
-            let combo_bonus = match n_combo {
-                0..6 => (n_combo as f32).powf(1.5 as f32) as i32,
-                _ => n_combo * 2,
-            };
-            Some(combo_bonus)
-        }
+        true => Some(combo_bonus(curr_combo + 1)),
         false => None,
     }
 }
+
+fn combo_bonus(n_combo: i32) -> i32 {
+    match n_combo {
+        0..6 => (n_combo as f32).powf(1.5 as f32) as i32,
+        _ => n_combo * 2,
+    }
+}
+
+fn time_bonus(area: &Area, combo: i32) -> f32 {
+    let combo_bonus = match combo {
+        n if n >= COMBO_TIME_BONUS_MIN => n.min(MAX_COMBO_TIME_BONUS) as f32,
+        _ => 0.,
+    };
+
+    let area_bonus = match area_multiplier(area) > AREA_TIME_BONUS_THRESHOLD {
+        true => AREA_TIME_BONUS,
+        false => 0.,
+    };
+
+    combo_bonus + area_bonus
+}
@@ -1,31 +1,88 @@
 use bevy::prelude::*;
 
-use crate::{despawn_screen, SystemState};
+use crate::{
+    actions::{Action, ActionState},
+    audio::Sfx,
+    despawn_screen,
+    menu::settings::GameConfig,
+    theme::ActiveTheme,
+    SystemState,
+};
 
-use super::GameState;
+use super::{
+    highscores::{table_key, HighScores},
+    GameState, InternalGameState,
+};
 
 pub fn starting_plugin(app: &mut App) {
     app.add_systems(OnEnter(GameState::Starting), game_starting_setup)
         .add_systems(OnExit(GameState::Starting), despawn_screen::<OnStarting>)
-        .add_systems(Update, countdown.run_if(in_state(GameState::Starting)));
+        .add_systems(
+            Update,
+            (skip_countdown, countdown)
+                .chain()
+                .run_if(in_state(GameState::Starting)),
+        );
 }
 
+// 3, 2, 1 for a second each, then GO! before the clock starts
+const COUNT_FROM: u32 = 3;
+const GO_SECS: f32 = 0.5;
+// each numeral lands from this many times its size
+const NUMERAL_START_SCALE: f32 = 2.5;
+
 // recurisvely despawn onstarting elements on exit
 #[derive(Component)]
 struct OnStarting;
 
+#[derive(Component)]
+struct CountdownText;
+
 // Newtype to use a `Timer` for this screen as a resource
 #[derive(Resource, Deref, DerefMut)]
-struct StartingTimer(Timer);
+pub struct StartingTimer(Timer);
+
+impl StartingTimer {
+    // how far through the numbers the countdown is, the board is fully revealed by GO!
+    pub fn reveal(&self) -> f32 {
+        (self.elapsed_secs() / COUNT_FROM as f32).min(1.)
+    }
+}
+
+fn game_starting_setup(
+    mut commands: Commands,
+    internal_game_state: Res<InternalGameState>,
+    high_scores: Res<HighScores>,
+    theme: Res<ActiveTheme>,
+) {
+    let game = &internal_game_state.0;
+
+    // the best score on this table is the one to beat
+    let target = high_scores
+        .tables
+        .get(&table_key(game))
+        .and_then(|table| table.first())
+        .map(|best| format!("beat {}", best.score))
+        .unwrap_or("set the first high score".to_string());
 
-fn game_starting_setup(mut commands: Commands) {
     commands
         .spawn((OnStarting, background()))
         .with_children(|parent| {
             spawn_count_down(parent);
+            parent.spawn((
+                Text::new(format!(
+                    "{}  ·  seed {}  ·  {}",
+                    game.mode, game.seed, target
+                )),
+                TextFont::from_font_size(20.),
+                TextColor(theme.muted_text),
+            ));
         });
 
-    commands.insert_resource(StartingTimer(Timer::from_seconds(3.0, TimerMode::Once)));
+    commands.insert_resource(StartingTimer(Timer::from_seconds(
+        COUNT_FROM as f32 + GO_SECS,
+        TimerMode::Once,
+    )));
 }
 
 fn background() -> (Node, BackgroundColor) {
@@ -46,19 +103,93 @@ fn background() -> (Node, BackgroundColor) {
 }
 
 fn spawn_count_down(parent: &mut ChildBuilder<'_>) {
-    parent.spawn(Text::new("Starting!!!"));
+    parent.spawn((
+        CountdownText,
+        Text::new(COUNT_FROM.to_string()),
+        TextFont::from_font_size(96.),
+    ));
 }
 
 fn go_main_menu(mut system_state: ResMut<NextState<SystemState>>) {
     system_state.set(SystemState::Menu);
 }
 
+// jumps straight to GO! rather than the board, so there's still a beat before the clock runs
+fn skip_countdown(
+    mut actions: ResMut<ActionState>,
+    touches: Res<Touches>,
+    mut timer: ResMut<StartingTimer>,
+) {
+    let skipped = actions.just_pressed(Action::Confirm)
+        || actions.just_pressed(Action::Select)
+        || touches.any_just_pressed();
+
+    if skipped && timer.elapsed_secs() < COUNT_FROM as f32 {
+        timer.set_elapsed(std::time::Duration::from_secs(COUNT_FROM as u64));
+        // the press was for the countdown, not the first move
+        actions.clear_just_pressed(Action::Confirm);
+        actions.clear_just_pressed(Action::Select);
+    }
+}
+
 fn countdown(
     mut game_state: ResMut<NextState<GameState>>,
     time: Res<Time>,
     mut timer: ResMut<StartingTimer>,
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
+    mut text: Query<(&mut Text, &mut TextColor, &mut Transform), With<CountdownText>>,
+    mut last_shown: Local<Option<u32>>,
+    mut sfx: EventWriter<Sfx>,
 ) {
     if timer.tick(time.delta()).finished() {
+        *last_shown = None;
         game_state.set(GameState::Playing);
+        return;
+    }
+
+    let elapsed = timer.elapsed_secs();
+    // 0 stands for GO!
+    let shown = COUNT_FROM.saturating_sub(elapsed as u32);
+    // how far through the current numeral, or through GO!
+    let progress = match shown {
+        0 => (elapsed - COUNT_FROM as f32) / GO_SECS,
+        _ => elapsed.fract(),
+    };
+
+    if *last_shown != Some(shown) {
+        *last_shown = Some(shown);
+        sfx.send(match shown {
+            0 => Sfx::Pop(0),
+            _ => Sfx::Tick,
+        });
+    }
+
+    let Ok((mut text, mut color, mut transform)) = text.get_single_mut() else {
+        return;
+    };
+
+    let label = match shown {
+        0 => "GO!".to_string(),
+        n => n.to_string(),
+    };
+    if text.0 != label {
+        text.0 = label;
     }
+
+    // numerals shrink into place and fade as the next one is due, GO! flashes bright then fades
+    let (scale, alpha, base) = match shown {
+        0 => (1. + progress * 0.5, 1. - progress, theme.accent),
+        _ => (
+            1. + (NUMERAL_START_SCALE - 1.) * (1. - progress).powi(3),
+            1. - progress * progress,
+            theme.text,
+        ),
+    };
+
+    transform.scale = match config.reduced_motion {
+        true => Vec3::ONE,
+        false => Vec3::splat(scale),
+    };
+    color.0 = base.with_alpha(alpha);
 }
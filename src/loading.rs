@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{
+        io::{file::FileAssetReader, AssetReaderError},
+        AssetLoadError, LoadState, UntypedAssetId,
+    },
+    prelude::*,
+};
+
+use crate::{
+    actions::{action_just_pressed, Action, Bindings},
+    despawn_screen,
+    theme::{theme_path, ActiveTheme, Theme, THEMES},
+    SystemState,
+};
+
+// every file the game reads from assets/ is requested here up front, and the game waits on this
+// screen until they have all either loaded or failed
+pub fn loading_plugin(app: &mut App) {
+    app.add_systems(PreStartup, load_assets)
+        .add_systems(OnEnter(SystemState::Loading), loading_setup)
+        .add_systems(
+            OnExit(SystemState::Loading),
+            despawn_screen::<OnLoadingScreen>,
+        )
+        .add_systems(
+            Update,
+            (
+                track_loading,
+                continue_without_missing.run_if(action_just_pressed(Action::Confirm)),
+            )
+                .chain()
+                .run_if(in_state(SystemState::Loading)),
+        );
+}
+
+const MENU_MUSIC: &str = "audio/menu.ogg";
+const GAME_MUSIC: &str = "audio/game.ogg";
+
+const BAR_SIZE: Vec2 = Vec2::new(400., 20.);
+
+#[derive(Resource)]
+pub struct GameAssets {
+    pub squaregg_chan: Handle<Image>,
+    pub egg: Handle<Image>,
+    // only there when the files are, otherwise the generated tunes play instead
+    pub menu_music: Option<Handle<AudioSource>>,
+    pub game_music: Option<Handle<AudioSource>>,
+    pub themes: HashMap<String, Handle<Theme>>,
+    tracked: Vec<TrackedAsset>,
+}
+
+struct TrackedAsset {
+    path: String,
+    id: UntypedAssetId,
+    required: bool, // the game can't go on without it
+}
+
+#[derive(Component)]
+struct OnLoadingScreen;
+
+#[derive(Component)]
+struct LoadingBarFill;
+
+#[derive(Component)]
+struct LoadingStatus;
+
+#[derive(Component)]
+struct MissingAssetsText;
+
+fn track<A: Asset>(
+    asset_server: &AssetServer,
+    tracked: &mut Vec<TrackedAsset>,
+    path: String,
+    required: bool,
+) -> Handle<A> {
+    let handle = asset_server.load::<A>(path.clone());
+    tracked.push(TrackedAsset {
+        path,
+        id: handle.id().untyped(),
+        required,
+    });
+    handle
+}
+
+// music is an optional extra, so it's only asked for when the file is there rather than failing
+// to load on every launch
+fn track_if_present<A: Asset>(
+    asset_server: &AssetServer,
+    tracked: &mut Vec<TrackedAsset>,
+    path: &str,
+) -> Option<Handle<A>> {
+    FileAssetReader::get_base_path()
+        .join("assets")
+        .join(path)
+        .is_file()
+        .then(|| track(asset_server, tracked, path.to_string(), false))
+}
+
+fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let mut tracked = Vec::new();
+
+    // text uses bevy's built in font and the sound effects are generated, so neither has files
+    commands.insert_resource(GameAssets {
+        squaregg_chan: track(
+            &asset_server,
+            &mut tracked,
+            "squaregg-chan.png".to_string(),
+            true,
+        ),
+        egg: track(&asset_server, &mut tracked, "egg.png".to_string(), true),
+        menu_music: track_if_present(&asset_server, &mut tracked, MENU_MUSIC),
+        game_music: track_if_present(&asset_server, &mut tracked, GAME_MUSIC),
+        themes: THEMES
+            .iter()
+            .map(|name| {
+                let handle = track(&asset_server, &mut tracked, theme_path(name), true);
+                (name.to_string(), handle)
+            })
+            .collect(),
+        tracked,
+    });
+}
+
+fn loading_setup(mut commands: Commands, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((
+            OnLoadingScreen,
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(20.),
+                height: Val::Percent(100.),
+                width: Val::Percent(100.),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text::new("Loading"));
+
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(BAR_SIZE.x),
+                        height: Val::Px(BAR_SIZE.y),
+                        border: UiRect::all(Val::Px(3.)),
+                        ..default()
+                    },
+                    BorderColor(Color::BLACK),
+                    BackgroundColor(theme.panel),
+                ))
+                .with_child((
+                    LoadingBarFill,
+                    Node {
+                        width: Val::Percent(0.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    BackgroundColor(theme.accent),
+                ));
+
+            parent.spawn((LoadingStatus, Text::default(), TextColor(theme.muted_text)));
+            parent.spawn((
+                MissingAssetsText,
+                Text::default(),
+                TextColor(theme.warning),
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+        });
+}
+
+// a short reason a file couldn't be loaded, a missing file being by far the most likely
+fn describe_failure(path: &str, error: &AssetLoadError) -> String {
+    match error {
+        AssetLoadError::AssetReaderError(AssetReaderError::NotFound(_)) => {
+            format!("assets/{} is missing", path)
+        }
+        error => format!("assets/{} couldn't be loaded: {}", path, error),
+    }
+}
+
+fn track_loading(
+    assets: Res<GameAssets>,
+    asset_server: Res<AssetServer>,
+    bindings: Res<Bindings>,
+    mut system_state: ResMut<NextState<SystemState>>,
+    mut fill: Query<&mut Node, With<LoadingBarFill>>,
+    mut status: Query<&mut Text, (With<LoadingStatus>, Without<MissingAssetsText>)>,
+    mut missing_text: Query<&mut Text, (With<MissingAssetsText>, Without<LoadingStatus>)>,
+    mut reported: Local<bool>,
+) {
+    let mut done = 0;
+    let mut missing = Vec::new();
+    let mut missing_required = false;
+
+    for asset in &assets.tracked {
+        match asset_server.load_state(asset.id) {
+            LoadState::Loaded => done += 1,
+            LoadState::Failed(error) => {
+                done += 1;
+                missing.push(describe_failure(&asset.path, &error));
+                missing_required |= asset.required;
+            }
+            LoadState::Loading | LoadState::NotLoaded => {}
+        }
+    }
+
+    let total = assets.tracked.len();
+    let progress = done as f32 / total.max(1) as f32;
+
+    if let Ok(mut node) = fill.get_single_mut() {
+        node.width = Val::Percent(progress * 100.);
+    }
+
+    if let Ok(mut text) = status.get_single_mut() {
+        text.0 = format!("{} / {} files", done, total);
+    }
+
+    if done < total {
+        return;
+    }
+
+    if !*reported {
+        *reported = true;
+        missing.iter().for_each(|failure| warn!("{}", failure));
+    }
+
+    // anything optional can be left out quietly, but the player should know before playing
+    // without art or themes
+    if !missing_required {
+        system_state.set(SystemState::Menu);
+        return;
+    }
+
+    if let Ok(mut text) = missing_text.get_single_mut() {
+        let confirm = bindings
+            .get(Action::Confirm)
+            .first()
+            .map(|binding| binding.to_string())
+            .unwrap_or("Confirm".to_string());
+        let message = format!(
+            "{}\n\nPress {} to continue without them",
+            missing.join("\n"),
+            confirm
+        );
+        if text.0 != message {
+            text.0 = message;
+        }
+    }
+}
+
+fn continue_without_missing(
+    assets: Res<GameAssets>,
+    asset_server: Res<AssetServer>,
+    mut system_state: ResMut<NextState<SystemState>>,
+) {
+    let finished = assets.tracked.iter().all(|asset| {
+        matches!(
+            asset_server.load_state(asset.id),
+            LoadState::Loaded | LoadState::Failed(_)
+        )
+    });
+
+    if finished {
+        system_state.set(SystemState::Menu);
+    }
+}
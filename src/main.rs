@@ -1,13 +1,19 @@
 use bevy::prelude::*;
 
+mod actions;
+mod audio;
 mod game;
+mod loading;
 mod menu;
 mod splash;
+mod storage;
+mod theme;
 
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum SystemState {
-    Splash,
     #[default]
+    Loading,
+    Splash,
     Menu,
     Game,
 }
@@ -27,7 +33,15 @@ fn main() {
         )
         .init_state::<SystemState>()
         .add_systems(Startup, setup)
-        .add_plugins((splash::splash_plugin, menu::menu_plugin, game::game_plugin))
+        .add_plugins((
+            actions::actions_plugin,
+            loading::loading_plugin,
+            audio::audio_plugin,
+            theme::theme_plugin,
+            splash::splash_plugin,
+            menu::menu_plugin,
+            game::game_plugin,
+        ))
         .run();
 }
 
@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    game::achievements::{Achievement, Achievements},
+    theme::{ActiveTheme, Theme},
+};
+
+use super::{default_text_style, spawn_button, MenuButtonAction, MenuState};
+
+pub fn achievements_plugin(app: &mut App) {
+    app.add_systems(OnEnter(MenuState::Achievements), achievements_setup)
+        .add_systems(
+            OnExit(MenuState::Achievements),
+            despawn_screen::<OnAchievementsMenuScreen>,
+        );
+}
+
+#[derive(Component)]
+struct OnAchievementsMenuScreen;
+
+fn achievements_setup(
+    mut commands: Commands,
+    achievements: Res<Achievements>,
+    theme: Res<ActiveTheme>,
+) {
+    commands
+        .spawn((OnAchievementsMenuScreen, background()))
+        .with_children(|parent| {
+            spawn_button(parent, MenuButtonAction::MainMenu, "Back To Menu");
+            parent.spawn(default_text_style(&format!(
+                "{} of {} unlocked",
+                achievements.unlocked.len(),
+                Achievement::ALL.len()
+            )));
+
+            Achievement::ALL.iter().for_each(|achievement| {
+                spawn_achievement(
+                    parent,
+                    achievement,
+                    achievements.unlocked.get(achievement),
+                    &theme,
+                );
+            });
+        });
+}
+
+fn spawn_achievement(
+    parent: &mut ChildBuilder<'_>,
+    achievement: &Achievement,
+    unlocked_on: Option<&String>,
+    theme: &Theme,
+) {
+    let (border, text_color, status) = match unlocked_on {
+        Some(date) => (theme.accent, theme.text, format!("Unlocked {}", date)),
+        None => (theme.button_hovered, theme.muted_text, "Locked".to_string()),
+    };
+
+    parent
+        .spawn((
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Px(500.),
+                border: UiRect::all(Val::Px(3.)),
+                padding: UiRect::axes(Val::Px(20.), Val::Px(8.)),
+                ..default()
+            },
+            BorderColor(border),
+            BorderRadius::all(Val::Px(10.)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new(achievement.title()), TextColor(text_color)));
+            parent.spawn((
+                Text::new(format!("{} - {}", achievement.description(), status)),
+                TextFont::from_font_size(14.),
+                TextColor(text_color),
+            ));
+        });
+}
+
+fn background() -> (Node, ZIndex, BackgroundColor) {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.),
+            height: Val::Percent(100.),
+            width: Val::Percent(100.),
+
+            ..Default::default()
+        },
+        ZIndex(i32::MIN),
+        BackgroundColor(Color::srgba(0., 0.2, 0.2, 0.0)),
+    )
+}
@@ -0,0 +1,139 @@
+use bevy::{input::InputSystem, prelude::*, ui::UiSystem};
+
+use crate::{
+    actions::{take_just_pressed_binding, update_actions, Action, Bindings},
+    despawn_screen,
+    theme::ActiveTheme,
+};
+
+use super::{spawn_button, MenuButtonAction, MenuState};
+
+pub fn controls_plugin(app: &mut App) {
+    app.add_systems(OnEnter(MenuState::Controls), controls_setup)
+        .add_systems(
+            OnExit(MenuState::Controls),
+            (despawn_screen::<OnControlsScreen>, stop_rebinding),
+        )
+        // before anything else sees the press, so binding a key doesn't also use it
+        .add_systems(
+            PreUpdate,
+            capture_binding
+                .after(InputSystem)
+                .before(update_actions)
+                .before(UiSystem::Focus)
+                .run_if(resource_exists::<Rebinding>),
+        )
+        .add_systems(
+            Update,
+            refresh_bindings
+                .run_if(in_state(MenuState::Controls).and(
+                    resource_changed::<Bindings>.or(resource_exists_and_changed::<Rebinding>),
+                )),
+        );
+}
+
+// the action waiting for its new key, the next press is bound to it
+#[derive(Resource)]
+pub struct Rebinding(pub Action);
+
+#[derive(Component)]
+struct OnControlsScreen;
+
+#[derive(Component)]
+struct BindingText(Action);
+
+fn controls_setup(mut commands: Commands, bindings: Res<Bindings>, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((
+            OnControlsScreen,
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.),
+                height: Val::Percent(100.),
+                width: Val::Percent(100.),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            spawn_button(parent, MenuButtonAction::Settings, "Back To Settings");
+
+            Action::ALL.iter().for_each(|action| {
+                parent
+                    .spawn((
+                        MenuButtonAction::Binding(*action),
+                        Button,
+                        Node {
+                            width: Val::Px(560.),
+                            height: Val::Px(40.),
+                            border: UiRect::all(Val::Px(3.0)),
+                            padding: UiRect::horizontal(Val::Px(20.)),
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BorderColor(Color::BLACK),
+                        BorderRadius::all(Val::Px(10.)),
+                    ))
+                    .with_children(|row| {
+                        row.spawn(Text::new(action.label()));
+                        row.spawn((
+                            BindingText(*action),
+                            Text::new(binding_names(&bindings, *action)),
+                            TextColor(theme.muted_text),
+                        ));
+                    });
+            });
+
+            spawn_button(parent, MenuButtonAction::ResetControls, "Reset Controls");
+        });
+}
+
+fn binding_names(bindings: &Bindings, action: Action) -> String {
+    match bindings.get(action) {
+        [] => "Unbound".to_string(),
+        bound => bound
+            .iter()
+            .map(|binding| binding.to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+fn refresh_bindings(
+    bindings: Res<Bindings>,
+    rebinding: Option<Res<Rebinding>>,
+    theme: Res<ActiveTheme>,
+    mut texts: Query<(&mut Text, &mut TextColor, &BindingText)>,
+) {
+    for (mut text, mut color, BindingText(action)) in &mut texts {
+        let listening = rebinding
+            .as_ref()
+            .is_some_and(|rebinding| rebinding.0 == *action);
+
+        (text.0, color.0) = match listening {
+            true => ("Press a key or button...".to_string(), theme.accent),
+            false => (binding_names(&bindings, *action), theme.muted_text),
+        };
+    }
+}
+
+fn capture_binding(
+    mut commands: Commands,
+    rebinding: Res<Rebinding>,
+    mut bindings: ResMut<Bindings>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    mut gamepads: Query<&mut Gamepad>,
+) {
+    if let Some(binding) = take_just_pressed_binding(&mut keys, &mut mouse, &mut gamepads) {
+        bindings.rebind(rebinding.0, binding);
+        commands.remove_resource::<Rebinding>();
+    }
+}
+
+fn stop_rebinding(mut commands: Commands) {
+    commands.remove_resource::<Rebinding>();
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    game::highscores::{spawn_high_score_table, HighScores},
+    theme::ActiveTheme,
+};
+
+use super::{default_text_style, spawn_button, MenuButtonAction, MenuState};
+
+pub fn highscores_plugin(app: &mut App) {
+    app.add_systems(OnEnter(MenuState::HighScores), highscores_setup)
+        .add_systems(
+            OnExit(MenuState::HighScores),
+            despawn_screen::<OnHighScoresMenuScreen>,
+        );
+}
+
+#[derive(Component)]
+struct OnHighScoresMenuScreen;
+
+fn highscores_setup(mut commands: Commands, high_scores: Res<HighScores>, theme: Res<ActiveTheme>) {
+    commands
+        .spawn((OnHighScoresMenuScreen, background()))
+        .with_children(|parent| {
+            spawn_button(parent, MenuButtonAction::MainMenu, "Back To Menu");
+
+            if high_scores.tables.is_empty() {
+                parent.spawn(default_text_style("No high scores yet, go play!"));
+            }
+
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(60.),
+                    row_gap: Val::Px(20.),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    high_scores.tables.iter().for_each(|(key, table)| {
+                        spawn_high_score_table(parent, key, table, None, &theme);
+                    });
+                });
+        });
+}
+
+fn background() -> (Node, ZIndex, BackgroundColor) {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.),
+            height: Val::Percent(100.),
+            width: Val::Percent(100.),
+
+            ..Default::default()
+        },
+        ZIndex(i32::MIN),
+        BackgroundColor(Color::srgba(0., 0.2, 0.2, 0.0)),
+    )
+}
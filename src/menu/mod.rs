@@ -1,15 +1,26 @@
 use bevy::prelude::*;
+use controls::Rebinding;
 use selection_option::SelectionButton;
+use settings::{GameConfig, SettingKind};
 
-use crate::{despawn_screen, SystemState};
+use crate::{
+    actions::{Action, Bindings},
+    despawn_screen,
+    loading::GameAssets,
+    SystemState,
+};
 
 mod about;
+mod achievements;
+mod controls;
+mod highscores;
 mod selection_option;
 pub(crate) mod settings;
+mod statistics;
+pub(crate) mod widgets;
 
 pub fn menu_plugin(app: &mut App) {
     app.init_state::<MenuState>()
-        .add_systems(Startup, load_images)
         .add_systems(OnEnter(SystemState::Menu), menu_setup)
         .add_systems(
             OnExit(SystemState::Menu),
@@ -19,8 +30,13 @@ pub fn menu_plugin(app: &mut App) {
         .add_systems(OnExit(MenuState::Main), despawn_screen::<OnMainMenuScreen>)
         .add_plugins((
             settings::settings_plugin,
+            controls::controls_plugin,
             about::about_plugin,
+            highscores::highscores_plugin,
+            statistics::statistics_plugin,
+            achievements::achievements_plugin,
             selection_option::selection_option,
+            widgets::widgets_plugin,
         ))
         .add_systems(
             Update,
@@ -28,24 +44,22 @@ pub fn menu_plugin(app: &mut App) {
         )
         .add_systems(
             Update,
-            check_squarea_loaded.run_if(in_state(MenuState::Loading)),
+            start_intro_sequence.run_if(in_state(MenuState::Loading)),
         )
         .add_systems(Update, (menu_action).run_if(in_state(SystemState::Menu)));
 }
 
-#[derive(Resource)]
-pub struct MenuImages {
-    // egg: Handle<Image>,
-    squarea_chan: Handle<Image>,
-}
-
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum MenuState {
     Loading,
     IntroSequence,
     Main,
     Settings,
+    Controls,
     About,
+    HighScores,
+    Statistics,
+    Achievements,
     #[default]
     Disabled,
 }
@@ -53,30 +67,13 @@ pub enum MenuState {
 #[derive(Resource)]
 pub struct MenuIntroSequenceTimer(Timer);
 
-fn load_images(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.insert_resource(MenuImages {
-        squarea_chan: asset_server.load("squaregg-chan.png"),
-    });
-}
-
-fn check_squarea_loaded(
-    menu_image: Res<MenuImages>,
-    asset_server: Res<AssetServer>,
-    mut menu_state: ResMut<NextState<MenuState>>,
-    // mut ev_writer: EventWriter<MenuIntroEvent>,
-    mut commands: Commands,
-) {
-    match asset_server.load_state(menu_image.squarea_chan.id()) {
-        bevy::asset::LoadState::Loaded => {
-            // ev_writer.send(MenuIntroEvent);
-            commands.insert_resource(MenuIntroSequenceTimer(Timer::from_seconds(
-                3.,
-                TimerMode::Once,
-            )));
-            menu_state.set(MenuState::IntroSequence)
-        }
-        _ => {}
-    }
+// the loading screen has already waited on the cover art, so the intro can start straight away
+fn start_intro_sequence(mut menu_state: ResMut<NextState<MenuState>>, mut commands: Commands) {
+    commands.insert_resource(MenuIntroSequenceTimer(Timer::from_seconds(
+        3.,
+        TimerMode::Once,
+    )));
+    menu_state.set(MenuState::IntroSequence)
 }
 
 fn menu_setup(mut menu_state: ResMut<NextState<MenuState>>) {
@@ -91,9 +88,16 @@ pub struct OnMainMenuScreen;
 pub enum MenuButtonAction {
     Play,
     Settings,
+    HighScores,
+    Statistics,
+    Achievements,
     About,
     Quit,
     MainMenu,
+    Setting(SettingKind),
+    Controls,
+    Binding(Action),
+    ResetControls,
 }
 
 #[derive(Component)]
@@ -102,14 +106,14 @@ struct BackgroundImage;
 #[derive(Component)]
 struct ButtonContainer;
 
-fn main_menu_setup(mut commands: Commands, menu_res: Res<MenuImages>) {
+fn main_menu_setup(mut commands: Commands, assets: Res<GameAssets>) {
     commands
         .spawn((OnMainMenuScreen, background()))
         .with_children(|background| {
             background
                 .spawn(menu_art_container())
                 .with_children(|art_container| {
-                    let squarea_chan = cover_image(menu_res);
+                    let squarea_chan = cover_image(&assets);
                     art_container.spawn((BackgroundImage, squarea_chan));
                 });
             background
@@ -117,6 +121,17 @@ fn main_menu_setup(mut commands: Commands, menu_res: Res<MenuImages>) {
                 .with_children(|button_container| {
                     spawn_button(button_container, MenuButtonAction::Play, "Play");
                     spawn_button(button_container, MenuButtonAction::Settings, "Settings");
+                    spawn_button(
+                        button_container,
+                        MenuButtonAction::HighScores,
+                        "High Scores",
+                    );
+                    spawn_button(button_container, MenuButtonAction::Statistics, "Statistics");
+                    spawn_button(
+                        button_container,
+                        MenuButtonAction::Achievements,
+                        "Achievements",
+                    );
                     spawn_button(button_container, MenuButtonAction::About, "About");
                     spawn_button(button_container, MenuButtonAction::Quit, "Quit");
                 });
@@ -161,7 +176,7 @@ pub fn apply_menu_action(
     mut game_state: ResMut<NextState<SystemState>>,
     mut selected_button: Query<&mut SelectionButton>,
     menu: Query<Entity, With<OnMainMenuScreen>>,
-    commands: Commands,
+    mut commands: Commands,
 ) {
     match menu_button_action {
         MenuButtonAction::Quit => {
@@ -175,7 +190,11 @@ pub fn apply_menu_action(
             if *curr_menu_state.get() == MenuState::IntroSequence {
                 despawn_screen(menu, commands);
             }
-            selected_button.single_mut().0 = MenuButtonAction::MainMenu;
+            // coming back from the controls screen keeps the egg on its button
+            selected_button.single_mut().0 = match curr_menu_state.get() {
+                MenuState::Controls => MenuButtonAction::Controls,
+                _ => MenuButtonAction::MainMenu,
+            };
             menu_state.set(MenuState::Settings)
         }
         MenuButtonAction::MainMenu => {
@@ -189,6 +208,39 @@ pub fn apply_menu_action(
             selected_button.single_mut().0 = MenuButtonAction::MainMenu;
             menu_state.set(MenuState::About)
         }
+        MenuButtonAction::HighScores => {
+            if *curr_menu_state.get() == MenuState::IntroSequence {
+                despawn_screen(menu, commands);
+            }
+            selected_button.single_mut().0 = MenuButtonAction::MainMenu;
+            menu_state.set(MenuState::HighScores)
+        }
+        MenuButtonAction::Statistics => {
+            if *curr_menu_state.get() == MenuState::IntroSequence {
+                despawn_screen(menu, commands);
+            }
+            selected_button.single_mut().0 = MenuButtonAction::MainMenu;
+            menu_state.set(MenuState::Statistics)
+        }
+        MenuButtonAction::Achievements => {
+            if *curr_menu_state.get() == MenuState::IntroSequence {
+                despawn_screen(menu, commands);
+            }
+            selected_button.single_mut().0 = MenuButtonAction::MainMenu;
+            menu_state.set(MenuState::Achievements)
+        }
+        // settings rows are adjusted by their own widgets
+        MenuButtonAction::Setting(_) => {}
+        MenuButtonAction::Controls => {
+            selected_button.single_mut().0 = MenuButtonAction::Settings;
+            menu_state.set(MenuState::Controls)
+        }
+        MenuButtonAction::Binding(action) => {
+            commands.insert_resource(Rebinding(*action));
+        }
+        MenuButtonAction::ResetControls => {
+            commands.insert_resource(Bindings::default());
+        }
     }
 }
 
@@ -200,6 +252,8 @@ pub fn spawn_button(parent: &mut ChildBuilder<'_>, component: impl Component, te
             Node {
                 width: Val::Px(300.0),
                 height: Val::Px(80.0),
+                // scrolling lists below give way instead
+                flex_shrink: 0.,
                 border: UiRect::all(Val::Px(5.0)),
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
@@ -272,9 +326,9 @@ fn menu_art_container() -> Node {
 //     }
 // }
 
-fn cover_image(images: Res<MenuImages>) -> (ImageNode, Node) {
+fn cover_image(assets: &GameAssets) -> (ImageNode, Node) {
     (
-        ImageNode::new(images.squarea_chan.clone()),
+        ImageNode::new(assets.squaregg_chan.clone()),
         Node {
             height: Val::Percent(100.),
             ..default()
@@ -302,7 +356,7 @@ fn buttons_container() -> Node {
         flex_direction: FlexDirection::Column,
         align_items: AlignItems::Center,
         justify_content: JustifyContent::Center,
-        row_gap: Val::Px(40.),
+        row_gap: Val::Px(12.),
         height: Val::Percent(100.),
         width: Val::Auto,
         ..Default::default()
@@ -320,8 +374,14 @@ fn menu_intro_animation(
     mut buttons_query: Query<&mut Node, (With<ButtonContainer>, Without<BackgroundImage>)>,
     mut menu_state: ResMut<NextState<MenuState>>,
     mut commands: Commands,
+    config: Res<GameConfig>,
 ) {
-    timer.0.tick(time.delta());
+    // with reduced motion the menu skips straight to where the intro would have left it
+    let delta = match config.reduced_motion {
+        true => timer.0.remaining(),
+        false => time.delta(),
+    };
+    timer.0.tick(delta);
 
     if timer.0.just_finished() {
         commands.remove_resource::<MenuIntroSequenceTimer>();
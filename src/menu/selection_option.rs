@@ -1,8 +1,20 @@
 use bevy::prelude::*;
 
-use crate::{despawn_screen, SystemState};
+use crate::{
+    actions::{Action, ActionState},
+    audio::Sfx,
+    despawn_screen,
+    loading::GameAssets,
+    theme::ActiveTheme,
+    SystemState,
+};
 
-use super::{apply_menu_action, ButtonContainer, MenuButtonAction, MenuState, OnMainMenuScreen};
+use super::{
+    apply_menu_action,
+    settings::{GameConfig, SETTINGS},
+    widgets::SettingsList,
+    ButtonContainer, MenuButtonAction, MenuState, OnMainMenuScreen,
+};
 
 pub fn selection_option(app: &mut App) {
     app.add_systems(OnEnter(SystemState::Menu), setup_selection_option)
@@ -13,7 +25,9 @@ pub fn selection_option(app: &mut App) {
                 button_system_mouse,
                 button_system_keyboard,
                 selected_option_system,
+                fit_eggs_to_selected_option,
                 animate_selected_option,
+                click_on_selection_change,
             )
                 .chain()
                 .run_if(in_state(SystemState::Menu)),
@@ -28,7 +42,7 @@ struct SelectedOptionEgg {
 #[derive(Component)]
 pub struct SelectionButton(pub MenuButtonAction);
 
-fn setup_selection_option(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_selection_option(mut commands: Commands, assets: Res<GameAssets>) {
     commands
         .spawn((
             SelectionButton(MenuButtonAction::Play),
@@ -48,7 +62,7 @@ fn setup_selection_option(mut commands: Commands, asset_server: Res<AssetServer>
             BackgroundColor(Color::srgba(0.2, 0.0, 0.1, 0.3)),
         ))
         .with_children(|parent| {
-            let egg = asset_server.load("egg.png");
+            let egg = assets.egg.clone();
             parent.spawn((
                 SelectedOptionEgg {
                     x_translation_anchor: 250.,
@@ -90,9 +104,6 @@ fn setup_selection_option(mut commands: Commands, asset_server: Res<AssetServer>
         });
 }
 
-const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
-
 // This system handles changing all buttons color based on mouse interaction
 fn button_system_mouse(
     mut interaction_query: Query<
@@ -105,22 +116,23 @@ fn button_system_mouse(
         (Changed<Interaction>, With<Button>),
     >,
     mut selected_button: Query<&mut SelectionButton, Without<Button>>,
+    theme: Res<ActiveTheme>,
 ) {
     for (interaction, mut bg_color, mab, e) in &mut interaction_query {
         bg_color.0 = match *interaction {
-            Interaction::None => NORMAL_BUTTON,
+            Interaction::None => theme.button,
             Interaction::Hovered => {
                 selected_button.single_mut().0 = mab.clone();
 
-                HOVERED_BUTTON
+                theme.button_hovered
             }
-            _ => NORMAL_BUTTON,
+            _ => theme.button,
         }
     }
 }
 
 fn button_system_keyboard(
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionState>,
     curr_menu_state: Res<State<MenuState>>,
     app_exit_events: EventWriter<AppExit>,
     menu_state: ResMut<NextState<MenuState>>,
@@ -129,7 +141,7 @@ fn button_system_keyboard(
     menu: Query<Entity, With<OnMainMenuScreen>>,
     commands: Commands,
 ) {
-    if keys.just_pressed(KeyCode::Enter) || keys.just_pressed(KeyCode::Space) {
+    if actions.just_pressed(Action::Confirm) {
         apply_menu_action(
             curr_menu_state,
             &selected_option.single().0.clone(),
@@ -143,86 +155,142 @@ fn button_system_keyboard(
         return;
     }
 
-    match curr_menu_state.get() {
-        MenuState::Main | MenuState::IntroSequence => {
-            let button_order = vec![
-                MenuButtonAction::Play,
-                MenuButtonAction::Settings,
-                MenuButtonAction::About,
-                MenuButtonAction::Quit,
-            ];
-
-            if let Some(curr_index) = button_order
-                .iter()
-                .position(|mba| *mba == selected_option.single().0)
-            {
-                if keys.just_pressed(KeyCode::ArrowUp) || keys.just_pressed(KeyCode::KeyW) {
-                    selected_option.single_mut().0 = button_order
-                        [(curr_index + button_order.len() - 1) % button_order.len()]
-                    .clone();
-
-                    // let thingo = buttons.iter().next().unwrap();
-                    // // println!("left: {:?}", buttons.iter().next().unwrap().3.left);
-                    // println!("top: {:?}", thingo.3.top);
-                    // println!("left: {:?}", thingo.3.left);
-                    // println!("translation: {:?}", thingo.0.translation);
-                    // // println!("left: {:?}", thingo.3.left);
-                }
+    let button_order = match curr_menu_state.get() {
+        MenuState::Main | MenuState::IntroSequence => vec![
+            MenuButtonAction::Play,
+            MenuButtonAction::Settings,
+            MenuButtonAction::HighScores,
+            MenuButtonAction::Statistics,
+            MenuButtonAction::Achievements,
+            MenuButtonAction::About,
+            MenuButtonAction::Quit,
+        ],
+        MenuState::Settings => [MenuButtonAction::MainMenu, MenuButtonAction::Controls]
+            .into_iter()
+            .chain(SETTINGS.iter().map(|kind| MenuButtonAction::Setting(*kind)))
+            .collect(),
+        MenuState::Controls => std::iter::once(MenuButtonAction::Settings)
+            .chain(
+                Action::ALL
+                    .iter()
+                    .map(|action| MenuButtonAction::Binding(*action)),
+            )
+            .chain(std::iter::once(MenuButtonAction::ResetControls))
+            .collect(),
+        _ => return,
+    };
 
-                if keys.just_pressed(KeyCode::ArrowDown) || keys.just_pressed(KeyCode::KeyS) {
-                    selected_option.single_mut().0 =
-                        button_order[(curr_index + 1) % button_order.len()].clone();
-                }
-            }
+    if let Some(curr_index) = button_order
+        .iter()
+        .position(|mba| *mba == selected_option.single().0)
+    {
+        if actions.just_pressed(Action::Up) {
+            selected_option.single_mut().0 =
+                button_order[(curr_index + button_order.len() - 1) % button_order.len()].clone();
+        }
+
+        if actions.just_pressed(Action::Down) {
+            selected_option.single_mut().0 =
+                button_order[(curr_index + 1) % button_order.len()].clone();
         }
-        _ => {}
     }
 }
 
 fn selected_option_system(
     mut buttons: Query<
-        (&Transform, &mut BackgroundColor, &MenuButtonAction),
+        (
+            &Transform,
+            &mut BackgroundColor,
+            &MenuButtonAction,
+            Option<&Parent>,
+        ),
         (With<Button>, Without<ButtonContainer>),
     >,
-    buttons_container: Query<&Transform, (Without<Button>, With<ButtonContainer>)>,
+    // buttons inside these are placed relative to them rather than the screen
+    buttons_containers: Query<&Transform, Or<(With<ButtonContainer>, With<SettingsList>)>>,
     mut selection_option: Query<
         (&mut Node, &SelectionButton),
         (Without<Button>, Without<ButtonContainer>),
     >,
+    theme: Res<ActiveTheme>,
 ) {
     buttons
         .iter_mut()
-        .for_each(|(button_transform, mut bg_color, menu_action)| {
+        .for_each(|(button_transform, mut bg_color, menu_action, parent)| {
             // let (mut selection_transform, selection) = selected_option.single_mut();
             if let Ok((mut style, selection)) = selection_option.get_single_mut() {
                 if *menu_action == selection.0 {
-                    let button_container_translation_x = buttons_container
-                        .get_single()
-                        .map(|transform| transform.translation.x)
-                        .ok();
-
-                    match button_container_translation_x {
-                        Some(x) => style.left = Val::Px(x + button_transform.translation.x),
-                        None => style.left = Val::Px(button_transform.translation.x),
-                    }
+                    let container_translation = parent
+                        .and_then(|parent| buttons_containers.get(parent.get()).ok())
+                        .map(|transform| transform.translation)
+                        .unwrap_or_default();
+
+                    style.left = Val::Px(container_translation.x + button_transform.translation.x);
                     // if let Some(x) = button_container_translation_x {
                     //     style.left = Val::Px(x + button_transform.translation.x);
                     // }
-                    style.top = Val::Px(button_transform.translation.y);
+                    style.top = Val::Px(container_translation.y + button_transform.translation.y);
 
-                    bg_color.0 = HOVERED_BUTTON;
+                    bg_color.0 = theme.button_hovered;
                 } else {
-                    bg_color.0 = NORMAL_BUTTON;
+                    bg_color.0 = theme.button;
                 }
             }
         });
 }
 
-fn animate_selected_option(time: Res<Time>, mut query: Query<(&mut Node, &SelectedOptionEgg)>) {
+// wider buttons like the settings rows push the eggs further out
+fn fit_eggs_to_selected_option(
+    buttons: Query<(&ComputedNode, &MenuButtonAction), With<Button>>,
+    selection_option: Query<&SelectionButton>,
+    mut eggs: Query<&mut SelectedOptionEgg>,
+) {
+    let Ok(selection) = selection_option.get_single() else {
+        return;
+    };
+
+    let Some((node, _)) = buttons.iter().find(|(_, action)| **action == selection.0) else {
+        return;
+    };
+
+    let half_width = node.size().x * node.inverse_scale_factor() / 2.;
+
+    for mut egg in &mut eggs {
+        egg.x_translation_anchor = egg.x_translation_anchor.signum() * (half_width + 100.);
+    }
+}
+
+fn animate_selected_option(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut query: Query<(&mut Node, &SelectedOptionEgg)>,
+) {
+    // the eggs hold still beside the option with reduced motion
+    let wobble = match config.reduced_motion {
+        true => 0.,
+        false => 15.0 * f32::sin(time.elapsed_secs() * 4.),
+    };
+
     for (mut style, egg) in &mut query {
-        style.left = Val::Px(
-            egg.x_translation_anchor.signum() * 15.0 * f32::sin(time.elapsed_secs() * 4.)
-                + egg.x_translation_anchor,
-        );
+        style.left = Val::Px(egg.x_translation_anchor.signum() * wobble + egg.x_translation_anchor);
+    }
+}
+
+fn click_on_selection_change(
+    selection_option: Query<&SelectionButton>,
+    mut last_selected: Local<Option<MenuButtonAction>>,
+    mut sfx: EventWriter<Sfx>,
+) {
+    let Ok(selection) = selection_option.get_single() else {
+        return;
+    };
+
+    // the first selection is where the egg starts, not a move
+    if last_selected
+        .as_ref()
+        .is_some_and(|last| *last != selection.0)
+    {
+        sfx.send(Sfx::Click);
     }
+    *last_selected = Some(selection.0.clone());
 }
@@ -1,26 +1,203 @@
+use std::path::PathBuf;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::despawn_screen;
+use crate::{
+    despawn_screen,
+    storage::{config_dir, load_ron, save_ron, srgba},
+    theme::{ActiveTheme, ColorVision, THEMES},
+};
 
-use super::{default_text_style, spawn_button, MenuButtonAction, MenuState};
+use super::{spawn_button, widgets::spawn_settings, MenuButtonAction, MenuState};
 
 pub fn settings_plugin(app: &mut App) {
     app.add_systems(OnEnter(MenuState::Settings), settings_setup)
         .add_systems(
             OnExit(MenuState::Settings),
-            despawn_screen::<OnSettingsMenuScreen>,
+            // written once the screen closes rather than on every change, so dragging a slider
+            // doesn't rewrite the file each frame
+            (despawn_screen::<OnSettingsMenuScreen>, save_config),
         )
-        .insert_resource(GameConfig::default());
+        .insert_resource(load_config());
 }
 
+// bump when a setting changes meaning, older files are still read with defaults for anything missing
+const CONFIG_VERSION: u32 = 1;
+
 #[derive(Component)]
 struct OnSettingsMenuScreen;
 
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameConfig {
     pub tile_size: f32,
     pub tile_gap: f32,
+    #[serde(with = "srgba")]
     pub tile_text_color: Color,
+    pub theme: String,
+    pub color_vision: ColorVision,
+    pub high_contrast: bool,
+    pub shape_cues: bool, // outlines and patterns so the board doesn't rely on colour alone
+    pub pop_style: PopStyle,
+    pub pop_params: PopParams,
+    pub reduced_motion: bool, // turns off pop animations and the moving menu decorations
+    // percentages, music and effects are both scaled by the master volume
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+}
+
+// how popped tiles leave the board
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum PopStyle {
+    #[default]
+    Explode,
+    Shrink,
+    Fade,
+    Dissolve,
+    None,
+}
+
+impl PopStyle {
+    pub const ALL: [PopStyle; 5] = [
+        PopStyle::Explode,
+        PopStyle::Shrink,
+        PopStyle::Fade,
+        PopStyle::Dissolve,
+        PopStyle::None,
+    ];
+
+    pub const NAMES: [&'static str; 5] = ["Explode", "Shrink", "Fade", "Dissolve", "None"];
+}
+
+// tuning for each pop style, only editable in the settings file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PopParams {
+    pub explode_gravity: f32,
+    pub explode_speed: f32, // the fastest a tile can be thrown, in pixels a second
+    pub explode_growth: f32, // how much bigger a tile gets each second
+    pub shrink_secs: f32,
+    pub fade_secs: f32,
+    pub dissolve_secs: f32,
+    pub dissolve_particles: u32, // per side, so a tile breaks into this many squared
+    pub dissolve_speed: f32,
+}
+
+impl Default for PopParams {
+    fn default() -> Self {
+        Self {
+            explode_gravity: 2000.,
+            explode_speed: 1200.,
+            explode_growth: 0.6,
+            shrink_secs: 0.25,
+            fade_secs: 0.4,
+            dissolve_secs: 0.6,
+            dissolve_particles: 3,
+            dissolve_speed: 150.,
+        }
+    }
+}
+
+impl GameConfig {
+    // the pop style to actually play, reduced motion wins over whatever style is picked
+    pub fn effective_pop_style(&self) -> PopStyle {
+        match self.reduced_motion {
+            true => PopStyle::None,
+            false => self.pop_style,
+        }
+    }
+
+    // the file is hand editable, so pull anything out of range back to what the widgets allow
+    fn sanitize(&mut self) {
+        let defaults = GameConfig::default();
+
+        for kind in SETTINGS {
+            if let Widget::Slider { min, max, .. } = kind.widget() {
+                let value = within(kind.value(self), min, max, kind.value(&defaults));
+                kind.set_value(self, value);
+            }
+        }
+
+        self.pop_params.sanitize();
+    }
+}
+
+impl PopParams {
+    // loose enough for any tuning worth trying, but an exploding tile always falls off the screen
+    // and a dissolve can't spawn thousands of particles
+    fn sanitize(&mut self) {
+        let defaults = PopParams::default();
+
+        self.explode_gravity = within(
+            self.explode_gravity,
+            100.,
+            10_000.,
+            defaults.explode_gravity,
+        );
+        self.explode_speed = within(self.explode_speed, 0., 5000., defaults.explode_speed);
+        self.explode_growth = within(self.explode_growth, 0., 5., defaults.explode_growth);
+        self.shrink_secs = within(self.shrink_secs, 0.05, 5., defaults.shrink_secs);
+        self.fade_secs = within(self.fade_secs, 0.05, 5., defaults.fade_secs);
+        self.dissolve_secs = within(self.dissolve_secs, 0.05, 5., defaults.dissolve_secs);
+        self.dissolve_particles = self.dissolve_particles.clamp(1, 8);
+        self.dissolve_speed = within(self.dissolve_speed, 0., 2000., defaults.dissolve_speed);
+    }
+}
+
+// clamps `value` to `min..=max`, a NaN from the file goes back to `default`
+fn within(value: f32, min: f32, max: f32, default: f32) -> f32 {
+    match value.is_nan() {
+        true => default,
+        false => value.clamp(min, max),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConfigFile {
+    version: u32,
+    #[serde(default)]
+    settings: GameConfig,
+}
+
+impl Default for ConfigFile {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            settings: GameConfig::default(),
+        }
+    }
+}
+
+fn config_path() -> PathBuf {
+    config_dir().join("settings.ron")
+}
+
+// falls back to the defaults if the file is missing or can't be parsed
+fn load_config() -> GameConfig {
+    let mut file = load_ron::<ConfigFile>(&config_path());
+
+    if file.version > CONFIG_VERSION {
+        warn!(
+            "settings file is version {} but this build only knows version {}, unknown settings are ignored",
+            file.version, CONFIG_VERSION
+        );
+    }
+
+    file.settings.sanitize();
+    file.settings
+}
+
+pub(crate) fn save_config(config: Res<GameConfig>) {
+    let file = ConfigFile {
+        version: CONFIG_VERSION,
+        settings: config.clone(),
+    };
+
+    if let Err(err) = save_ron(&config_path(), &file) {
+        warn!("couldn't save settings: {}", err);
+    }
 }
 
 impl Default for GameConfig {
@@ -29,23 +206,243 @@ impl Default for GameConfig {
             tile_size: 40.,
             tile_gap: 5.,
             tile_text_color: Color::hsl(0., 0., 0.1),
+            theme: THEMES[0].to_string(),
+            color_vision: ColorVision::Standard,
+            high_contrast: false,
+            shape_cues: false,
+            pop_style: PopStyle::default(),
+            pop_params: PopParams::default(),
+            reduced_motion: false,
+            master_volume: 80.,
+            music_volume: 60.,
+            sfx_volume: 80.,
+        }
+    }
+}
+
+// every setting on the settings screen, in the order the keyboard moves through them
+pub const SETTINGS: [SettingKind; 12] = [
+    SettingKind::Theme,
+    SettingKind::ColorVision,
+    SettingKind::HighContrast,
+    SettingKind::ShapeCues,
+    SettingKind::ReducedMotion,
+    SettingKind::PopStyle,
+    SettingKind::TileSize,
+    SettingKind::TileGap,
+    SettingKind::TileTextColor,
+    SettingKind::MasterVolume,
+    SettingKind::MusicVolume,
+    SettingKind::SfxVolume,
+];
+
+// the pause menu leaves out anything that would move the tiles already on the board, those take
+// effect from the next game
+pub fn in_game_settings() -> Vec<SettingKind> {
+    SETTINGS
+        .into_iter()
+        .filter(|kind| !matches!(kind, SettingKind::TileSize | SettingKind::TileGap))
+        .collect()
+}
+
+// dark enough to read on the light tiles
+const TILE_TEXT_COLORS: [Color; 6] = [
+    Color::hsl(0., 0., 0.1),
+    Color::hsl(220., 0.6, 0.35),
+    Color::hsl(0., 0.6, 0.4),
+    Color::hsl(140., 0.5, 0.3),
+    Color::hsl(280., 0.5, 0.4),
+    Color::hsl(30., 0.8, 0.4),
+];
+
+// the palette is hsl but a loaded colour comes back as srgba with some rounding, so compare them
+// in the same space with a little slack
+fn same_color(a: Color, b: Color) -> bool {
+    let (a, b) = (a.to_srgba(), b.to_srgba());
+    [
+        a.red - b.red,
+        a.green - b.green,
+        a.blue - b.blue,
+        a.alpha - b.alpha,
+    ]
+    .iter()
+    .all(|difference| difference.abs() < 1e-3)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum SettingKind {
+    Theme,
+    ColorVision,
+    HighContrast,
+    ShapeCues,
+    ReducedMotion,
+    PopStyle,
+    TileSize,
+    TileGap,
+    TileTextColor,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+}
+
+// how a setting is shown and edited
+pub enum Widget {
+    Slider {
+        min: f32,
+        max: f32,
+        step: f32,
+        unit: &'static str,
+    },
+    Stepper {
+        options: &'static [&'static str],
+    },
+    Toggle,
+    Palette(&'static [Color]),
+}
+
+impl SettingKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingKind::Theme => "Theme",
+            SettingKind::ColorVision => "Colour vision",
+            SettingKind::HighContrast => "High contrast",
+            SettingKind::ShapeCues => "Shape cues",
+            SettingKind::ReducedMotion => "Reduced motion",
+            SettingKind::PopStyle => "Pop style",
+            SettingKind::TileSize => "Tile size",
+            SettingKind::TileGap => "Tile gap",
+            SettingKind::TileTextColor => "Tile text",
+            SettingKind::MasterVolume => "Master volume",
+            SettingKind::MusicVolume => "Music volume",
+            SettingKind::SfxVolume => "Effects volume",
+        }
+    }
+
+    pub fn widget(&self) -> Widget {
+        match self {
+            SettingKind::Theme => Widget::Stepper { options: &THEMES },
+            SettingKind::ColorVision => Widget::Stepper {
+                options: &ColorVision::NAMES,
+            },
+            SettingKind::HighContrast | SettingKind::ShapeCues | SettingKind::ReducedMotion => {
+                Widget::Toggle
+            }
+            SettingKind::PopStyle => Widget::Stepper {
+                options: &PopStyle::NAMES,
+            },
+            SettingKind::TileSize => Widget::Slider {
+                min: 20.,
+                max: 80.,
+                step: 1.,
+                unit: "px",
+            },
+            SettingKind::TileGap => Widget::Slider {
+                min: 0.,
+                max: 20.,
+                step: 1.,
+                unit: "px",
+            },
+            SettingKind::TileTextColor => Widget::Palette(&TILE_TEXT_COLORS),
+            SettingKind::MasterVolume | SettingKind::MusicVolume | SettingKind::SfxVolume => {
+                Widget::Slider {
+                    min: 0.,
+                    max: 100.,
+                    step: 10.,
+                    unit: "%",
+                }
+            }
+        }
+    }
+
+    // sliders hold their value, steppers and palettes an index and toggles 0 or 1
+    pub fn value(&self, config: &GameConfig) -> f32 {
+        match self {
+            SettingKind::Theme => THEMES
+                .iter()
+                .position(|theme| *theme == config.theme)
+                .unwrap_or(0) as f32,
+            SettingKind::ColorVision => ColorVision::ALL
+                .iter()
+                .position(|vision| *vision == config.color_vision)
+                .unwrap_or(0) as f32,
+            SettingKind::HighContrast => config.high_contrast as u8 as f32,
+            SettingKind::ShapeCues => config.shape_cues as u8 as f32,
+            SettingKind::ReducedMotion => config.reduced_motion as u8 as f32,
+            SettingKind::PopStyle => PopStyle::ALL
+                .iter()
+                .position(|style| *style == config.pop_style)
+                .unwrap_or(0) as f32,
+            SettingKind::TileSize => config.tile_size,
+            SettingKind::TileGap => config.tile_gap,
+            SettingKind::MasterVolume => config.master_volume,
+            SettingKind::MusicVolume => config.music_volume,
+            SettingKind::SfxVolume => config.sfx_volume,
+            SettingKind::TileTextColor => TILE_TEXT_COLORS
+                .iter()
+                .position(|color| same_color(*color, config.tile_text_color))
+                .unwrap_or(0) as f32,
+        }
+    }
+
+    pub fn set_value(&self, config: &mut GameConfig, value: f32) {
+        match self {
+            SettingKind::Theme => config.theme = THEMES[value as usize % THEMES.len()].to_string(),
+            SettingKind::ColorVision => {
+                config.color_vision = ColorVision::ALL[value as usize % ColorVision::ALL.len()]
+            }
+            SettingKind::HighContrast => config.high_contrast = value > 0.,
+            SettingKind::ShapeCues => config.shape_cues = value > 0.,
+            SettingKind::ReducedMotion => config.reduced_motion = value > 0.,
+            SettingKind::PopStyle => {
+                config.pop_style = PopStyle::ALL[value as usize % PopStyle::ALL.len()]
+            }
+            SettingKind::TileSize => config.tile_size = value,
+            SettingKind::TileGap => config.tile_gap = value,
+            SettingKind::MasterVolume => config.master_volume = value,
+            SettingKind::MusicVolume => config.music_volume = value,
+            SettingKind::SfxVolume => config.sfx_volume = value,
+            SettingKind::TileTextColor => {
+                config.tile_text_color = TILE_TEXT_COLORS[value as usize % TILE_TEXT_COLORS.len()]
+            }
+        }
+    }
+
+    pub fn display(&self, config: &GameConfig) -> String {
+        let value = self.value(config);
+
+        match self.widget() {
+            Widget::Slider { unit, .. } => format!("{}{}", value, unit),
+            Widget::Stepper { options } => options[value as usize].to_string(),
+            Widget::Toggle => match value > 0. {
+                true => "On".to_string(),
+                false => "Off".to_string(),
+            },
+            Widget::Palette(colors) => format!("{}/{}", value as usize + 1, colors.len()),
         }
     }
+
+    // one keyboard press or stepper click in `direction`, either -1 or 1
+    pub fn step(&self, config: &mut GameConfig, direction: f32) {
+        let value = self.value(config);
+
+        let next = match self.widget() {
+            Widget::Slider { min, max, step, .. } => (value + direction * step).clamp(min, max),
+            Widget::Stepper { options } => (value + direction).rem_euclid(options.len() as f32),
+            Widget::Toggle => 1. - value,
+            Widget::Palette(colors) => (value + direction).rem_euclid(colors.len() as f32),
+        };
+
+        self.set_value(config, next);
+    }
 }
 
-fn settings_setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    config: ResMut<GameConfig>,
-) {
+fn settings_setup(mut commands: Commands, config: Res<GameConfig>, theme: Res<ActiveTheme>) {
     commands
         .spawn((OnSettingsMenuScreen, background()))
         .with_children(|parent| {
             spawn_button(parent, MenuButtonAction::MainMenu, "Back To Menu");
-            parent.spawn(default_text_style("settings"));
-            parent.spawn(default_text_style(
-                format!("{:?}", config).to_string().as_str(),
-            ));
+            spawn_button(parent, MenuButtonAction::Controls, "Controls");
+            spawn_settings(parent, &SETTINGS, &config, &theme);
         });
 }
 
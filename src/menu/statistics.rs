@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    game::lifetime_stats::{LifetimeStats, HISTOGRAM_BUCKET},
+    theme::ActiveTheme,
+};
+
+use super::{default_text_style, spawn_button, MenuButtonAction, MenuState};
+
+pub fn statistics_plugin(app: &mut App) {
+    app.add_systems(OnEnter(MenuState::Statistics), statistics_setup)
+        .add_systems(
+            OnExit(MenuState::Statistics),
+            despawn_screen::<OnStatisticsMenuScreen>,
+        );
+}
+
+// tallest bar in the bar charts
+const BAR_HEIGHT: f32 = 120.;
+const BAR_WIDTH: f32 = 28.;
+
+#[derive(Component)]
+struct OnStatisticsMenuScreen;
+
+fn statistics_setup(mut commands: Commands, stats: Res<LifetimeStats>, theme: Res<ActiveTheme>) {
+    let average_score = match stats.average_score() {
+        Some(average) => format!("{:.1}", average),
+        None => "-".to_string(),
+    };
+
+    let common_sizes = match stats.common_rect_sizes(5) {
+        sizes if sizes.is_empty() => "-".to_string(),
+        sizes => sizes
+            .iter()
+            .map(|(size, count)| format!("{} ({})", size, count))
+            .collect::<Vec<String>>()
+            .join(", "),
+    };
+
+    commands
+        .spawn((OnStatisticsMenuScreen, background()))
+        .with_children(|parent| {
+            spawn_button(parent, MenuButtonAction::MainMenu, "Back To Menu");
+            parent.spawn(default_text_style(&format!(
+                "Games played: {}    Tiles popped: {}",
+                stats.games_played, stats.total_tiles_popped
+            )));
+            parent.spawn(default_text_style(&format!(
+                "Average score: {}    Best score: {}",
+                average_score, stats.best_score
+            )));
+            parent.spawn(default_text_style(&format!(
+                "Most common rectangles: {}",
+                common_sizes
+            )));
+
+            parent.spawn(row()).with_children(|parent| {
+                spawn_bar_chart(
+                    parent,
+                    "Scores",
+                    stats
+                        .score_histogram
+                        .iter()
+                        .map(|(bucket, games)| (bucket.to_string(), *games))
+                        .collect(),
+                    theme.accent,
+                );
+                spawn_bar_chart(
+                    parent,
+                    "Tile values popped",
+                    stats
+                        .tile_values
+                        .iter()
+                        .enumerate()
+                        .map(|(i, count)| ((i + 1).to_string(), *count))
+                        .collect(),
+                    theme.accent,
+                );
+            });
+            parent.spawn(default_text_style(&format!(
+                "Scores are grouped in buckets of {}",
+                HISTOGRAM_BUCKET
+            )));
+        });
+}
+
+fn spawn_bar_chart(
+    parent: &mut ChildBuilder<'_>,
+    title: &str,
+    bars: Vec<(String, u32)>,
+    color: Color,
+) {
+    let tallest = bars
+        .iter()
+        .map(|(_, value)| *value)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    parent
+        .spawn(Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(default_text_style(title));
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::End,
+                    column_gap: Val::Px(6.),
+                    height: Val::Px(BAR_HEIGHT + 60.),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    bars.iter().for_each(|(label, value)| {
+                        parent
+                            .spawn(Node {
+                                display: Display::Flex,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(value.to_string()),
+                                    TextFont::from_font_size(12.),
+                                ));
+                                parent.spawn((
+                                    Node {
+                                        width: Val::Px(BAR_WIDTH),
+                                        height: Val::Px(
+                                            BAR_HEIGHT * *value as f32 / tallest as f32,
+                                        ),
+                                        ..default()
+                                    },
+                                    BackgroundColor(color),
+                                ));
+                                parent.spawn((
+                                    Text::new(label.as_str()),
+                                    TextFont::from_font_size(12.),
+                                ));
+                            });
+                    });
+                });
+        });
+}
+
+fn row() -> Node {
+    Node {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::End,
+        column_gap: Val::Px(80.),
+        ..default()
+    }
+}
+
+fn background() -> (Node, ZIndex, BackgroundColor) {
+    (
+        Node {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(20.),
+            height: Val::Percent(100.),
+            width: Val::Percent(100.),
+
+            ..Default::default()
+        },
+        ZIndex(i32::MIN),
+        BackgroundColor(Color::srgba(0., 0.2, 0.2, 0.0)),
+    )
+}
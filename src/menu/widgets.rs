@@ -0,0 +1,480 @@
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::RelativeCursorPosition,
+};
+
+use crate::{
+    actions::{Action, ActionState},
+    theme::{ActiveTheme, Theme},
+};
+
+use super::{
+    selection_option::SelectionButton,
+    settings::{GameConfig, SettingKind, Widget},
+    MenuButtonAction, MenuState,
+};
+
+pub fn widgets_plugin(app: &mut App) {
+    // not tied to the menu state so the pause menu settings work too
+    app.init_resource::<FocusedSetting>()
+        .add_systems(OnExit(MenuState::Settings), clear_focused_setting)
+        .add_systems(
+            Update,
+            (
+                drag_sliders,
+                press_steppers,
+                press_swatches,
+                press_toggles,
+                focus_egg_setting.run_if(in_state(MenuState::Settings)),
+                adjust_selected_setting,
+                scroll_to_focused_setting.run_if(resource_changed::<FocusedSetting>),
+                scroll_settings_with_wheel.run_if(on_event::<MouseWheel>),
+                (refresh_widgets, rebuild_preview)
+                    .run_if(resource_changed::<GameConfig>.or(resource_changed::<ActiveTheme>)),
+                restyle_widgets.run_if(resource_changed::<ActiveTheme>),
+            )
+                .chain(),
+        );
+}
+
+const ROW_WIDTH: f32 = 560.;
+const ROW_HEIGHT: f32 = 60.;
+const ROW_GAP: f32 = 20.;
+// how far one notch of a mouse wheel scrolls the settings list
+const WHEEL_LINE_HEIGHT: f32 = 40.;
+const SLIDER_WIDTH: f32 = 220.;
+const SWATCH_SIZE: f32 = 28.;
+
+// a few tiles that sum to 10 so the preview looks like a real board
+const PREVIEW_TILES: [[i32; 6]; 3] = [[3, 7, 1, 4, 5, 9], [2, 8, 6, 2, 2, 1], [5, 5, 4, 3, 3, 8]];
+
+// the inner buttons of a setting row, these keep their own colours instead of the hover ones
+#[derive(Component, Default)]
+pub struct SettingWidget;
+
+#[derive(Component)]
+struct SettingValueText(SettingKind);
+
+#[derive(Component)]
+struct SliderTrack(SettingKind);
+
+#[derive(Component)]
+struct SliderFill(SettingKind);
+
+#[derive(Component)]
+struct StepButton(SettingKind, f32);
+
+#[derive(Component)]
+struct Swatch(SettingKind, usize);
+
+#[derive(Component)]
+struct PreviewBoard;
+
+// the setting row the keyboard is on, picked by the egg selector in the menu or the pause menu's
+// own selection in game. left and right adjust it and the settings list keeps it in view
+#[derive(Resource, Default, PartialEq)]
+pub struct FocusedSetting(pub Option<SettingKind>);
+
+// the rows don't fit a small window, so they scroll with the preview board at the bottom
+#[derive(Component)]
+pub struct SettingsList(Vec<SettingKind>);
+
+// settings contents shared between the main menu and the in game pause menu
+pub fn spawn_settings(
+    parent: &mut ChildBuilder<'_>,
+    settings: &[SettingKind],
+    config: &GameConfig,
+    theme: &Theme,
+) {
+    parent
+        .spawn((
+            SettingsList(settings.to_vec()),
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(ROW_GAP),
+                // shrinks to whatever the buttons above leave
+                min_height: Val::Px(0.),
+                overflow: Overflow::scroll_y(),
+                ..default()
+            },
+        ))
+        .with_children(|list| {
+            settings
+                .iter()
+                .for_each(|kind| spawn_setting_row(list, *kind, config, theme));
+            spawn_preview_board(list, config, theme);
+        });
+}
+
+fn spawn_setting_row(
+    parent: &mut ChildBuilder<'_>,
+    kind: SettingKind,
+    config: &GameConfig,
+    theme: &Theme,
+) {
+    parent
+        .spawn((
+            MenuButtonAction::Setting(kind),
+            Button,
+            Node {
+                width: Val::Px(ROW_WIDTH),
+                height: Val::Px(ROW_HEIGHT),
+                flex_shrink: 0.,
+                border: UiRect::all(Val::Px(5.0)),
+                padding: UiRect::horizontal(Val::Px(20.)),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(16.),
+                ..default()
+            },
+            BorderColor(Color::BLACK),
+            BorderRadius::all(Val::Px(10.)),
+        ))
+        .with_children(|row| {
+            row.spawn(Text::new(kind.label()));
+
+            row.spawn(Node {
+                display: Display::Flex,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.),
+                ..default()
+            })
+            .with_children(|widget| {
+                match kind.widget() {
+                    Widget::Slider { min, max, .. } => {
+                        let fraction = (kind.value(config) - min) / (max - min);
+                        widget
+                            .spawn((
+                                SliderTrack(kind),
+                                SettingWidget,
+                                Button,
+                                RelativeCursorPosition::default(),
+                                Node {
+                                    width: Val::Px(SLIDER_WIDTH),
+                                    height: Val::Px(14.),
+                                    ..default()
+                                },
+                                BackgroundColor(theme.button_hovered),
+                                BorderRadius::all(Val::Px(7.)),
+                            ))
+                            .with_child((
+                                SliderFill(kind),
+                                Node {
+                                    width: Val::Percent(100. * fraction),
+                                    height: Val::Percent(100.),
+                                    ..default()
+                                },
+                                BackgroundColor(theme.accent),
+                                BorderRadius::all(Val::Px(7.)),
+                            ));
+                    }
+                    Widget::Stepper { .. } => {
+                        spawn_step_button(widget, kind, -1., "<", theme);
+                        spawn_step_button(widget, kind, 1., ">", theme);
+                    }
+                    Widget::Toggle => {}
+                    Widget::Palette(colors) => {
+                        let selected = kind.value(config) as usize;
+                        colors.iter().enumerate().for_each(|(index, color)| {
+                            widget.spawn((
+                                Swatch(kind, index),
+                                SettingWidget,
+                                Button,
+                                Node {
+                                    width: Val::Px(SWATCH_SIZE),
+                                    height: Val::Px(SWATCH_SIZE),
+                                    border: UiRect::all(Val::Px(3.)),
+                                    ..default()
+                                },
+                                BackgroundColor(*color),
+                                BorderColor(swatch_border(index == selected, theme)),
+                                BorderRadius::all(Val::Px(6.)),
+                            ));
+                        });
+                    }
+                }
+
+                widget.spawn((
+                    SettingValueText(kind),
+                    Text::new(kind.display(config)),
+                    Node {
+                        min_width: Val::Px(60.),
+                        ..default()
+                    },
+                ));
+            });
+        });
+}
+
+fn spawn_step_button(
+    parent: &mut ChildBuilder<'_>,
+    kind: SettingKind,
+    direction: f32,
+    text: &str,
+    theme: &Theme,
+) {
+    parent
+        .spawn((
+            StepButton(kind, direction),
+            SettingWidget,
+            Button,
+            Node {
+                width: Val::Px(32.),
+                height: Val::Px(32.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(theme.button_hovered),
+            BorderRadius::all(Val::Px(6.)),
+        ))
+        .with_child(Text::new(text));
+}
+
+fn swatch_border(selected: bool, theme: &Theme) -> Color {
+    match selected {
+        true => theme.text,
+        false => theme.button,
+    }
+}
+
+// a few tiles drawn with the current settings so changes can be seen straight away
+fn spawn_preview_board(parent: &mut ChildBuilder<'_>, config: &GameConfig, theme: &Theme) {
+    parent
+        .spawn((
+            PreviewBoard,
+            Node {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                flex_shrink: 0.,
+                ..default()
+            },
+        ))
+        .with_children(|board| spawn_preview_tiles(board, config, theme));
+}
+
+fn spawn_preview_tiles(board: &mut ChildBuilder<'_>, config: &GameConfig, theme: &Theme) {
+    PREVIEW_TILES.iter().for_each(|row| {
+        board
+            .spawn(Node {
+                display: Display::Flex,
+                column_gap: Val::Px(config.tile_gap),
+                margin: UiRect::bottom(Val::Px(config.tile_gap)),
+                ..default()
+            })
+            .with_children(|row_node| {
+                row.iter().for_each(|val| {
+                    row_node
+                        .spawn((
+                            Node {
+                                width: Val::Px(config.tile_size),
+                                height: Val::Px(config.tile_size),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(theme.tile),
+                        ))
+                        .with_child((
+                            Text::new(val.to_string()),
+                            TextColor(config.tile_text_color),
+                        ));
+                });
+            });
+    });
+}
+
+// holding the mouse down on a slider follows the cursor
+fn drag_sliders(
+    mut config: ResMut<GameConfig>,
+    sliders: Query<(&Interaction, &RelativeCursorPosition, &SliderTrack)>,
+) {
+    for (interaction, cursor, SliderTrack(kind)) in &sliders {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if let (Some(position), Widget::Slider { min, max, step, .. }) =
+            (cursor.normalized, kind.widget())
+        {
+            let fraction = position.x.clamp(0., 1.);
+            let value = min + ((max - min) * fraction / step).round() * step;
+
+            let mut next = config.clone();
+            kind.set_value(&mut next, value);
+            config.set_if_neq(next);
+        }
+    }
+}
+
+fn press_steppers(
+    mut config: ResMut<GameConfig>,
+    steppers: Query<(&Interaction, &StepButton), Changed<Interaction>>,
+) {
+    for (interaction, StepButton(kind, direction)) in &steppers {
+        if *interaction == Interaction::Pressed {
+            kind.step(&mut config, *direction);
+        }
+    }
+}
+
+fn press_swatches(
+    mut config: ResMut<GameConfig>,
+    swatches: Query<(&Interaction, &Swatch), Changed<Interaction>>,
+) {
+    for (interaction, Swatch(kind, index)) in &swatches {
+        if *interaction == Interaction::Pressed {
+            // picking the colour that's already chosen changes nothing
+            let mut next = config.clone();
+            kind.set_value(&mut next, *index as f32);
+            config.set_if_neq(next);
+        }
+    }
+}
+
+fn press_toggles(
+    mut config: ResMut<GameConfig>,
+    rows: Query<(&Interaction, &MenuButtonAction), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, action) in &rows {
+        if let (Interaction::Pressed, MenuButtonAction::Setting(kind)) = (interaction, action) {
+            if let Widget::Toggle = kind.widget() {
+                kind.step(&mut config, 1.);
+            }
+        }
+    }
+}
+
+fn focus_egg_setting(
+    selected_option: Query<&SelectionButton>,
+    mut focused: ResMut<FocusedSetting>,
+) {
+    let kind = match selected_option.get_single() {
+        Ok(SelectionButton(MenuButtonAction::Setting(kind))) => Some(*kind),
+        _ => None,
+    };
+
+    focused.set_if_neq(FocusedSetting(kind));
+}
+
+// run when either settings screen closes so left and right stop adjusting it
+pub fn clear_focused_setting(mut focused: ResMut<FocusedSetting>) {
+    focused.set_if_neq(FocusedSetting(None));
+}
+
+// left and right adjust whichever setting is focused
+fn adjust_selected_setting(
+    actions: Res<ActionState>,
+    mut config: ResMut<GameConfig>,
+    focused: Res<FocusedSetting>,
+) {
+    let FocusedSetting(Some(kind)) = *focused else {
+        return;
+    };
+
+    let direction = match kind.widget() {
+        Widget::Toggle if actions.just_pressed(Action::Confirm) => 1.,
+        _ if actions.just_pressed(Action::Left) => -1.,
+        _ if actions.just_pressed(Action::Right) => 1.,
+        _ => return,
+    };
+
+    kind.step(&mut config, direction);
+}
+
+// every row is the same height, so where one sits in the list follows from its index
+fn scroll_to_focused_setting(
+    focused: Res<FocusedSetting>,
+    mut lists: Query<(&SettingsList, &ComputedNode, &mut ScrollPosition)>,
+) {
+    let FocusedSetting(Some(kind)) = *focused else {
+        return;
+    };
+
+    for (SettingsList(settings), node, mut scroll) in &mut lists {
+        let Some(index) = settings.iter().position(|setting| *setting == kind) else {
+            continue;
+        };
+
+        // not laid out yet on the frame the screen opens
+        let visible = node.size().y * node.inverse_scale_factor();
+        if visible <= 0. {
+            continue;
+        }
+
+        let top = index as f32 * (ROW_HEIGHT + ROW_GAP);
+
+        if top < scroll.offset_y {
+            scroll.offset_y = top;
+        } else if top + ROW_HEIGHT > scroll.offset_y + visible {
+            scroll.offset_y = top + ROW_HEIGHT - visible;
+        }
+    }
+}
+
+// the layout keeps the offset within the list, so this doesn't need to clamp it
+fn scroll_settings_with_wheel(
+    mut wheel: EventReader<MouseWheel>,
+    mut lists: Query<&mut ScrollPosition, With<SettingsList>>,
+) {
+    let scrolled: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y * WHEEL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        })
+        .sum();
+
+    for mut scroll in &mut lists {
+        scroll.offset_y -= scrolled;
+    }
+}
+
+fn refresh_widgets(
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
+    mut value_texts: Query<(&mut Text, &SettingValueText)>,
+    mut slider_fills: Query<(&mut Node, &mut BackgroundColor, &SliderFill)>,
+    mut swatches: Query<(&mut BorderColor, &Swatch)>,
+) {
+    for (mut text, SettingValueText(kind)) in &mut value_texts {
+        text.0 = kind.display(&config);
+    }
+
+    for (mut node, mut background, SliderFill(kind)) in &mut slider_fills {
+        if let Widget::Slider { min, max, .. } = kind.widget() {
+            node.width = Val::Percent(100. * (kind.value(&config) - min) / (max - min));
+        }
+        background.0 = theme.accent;
+    }
+
+    for (mut border, Swatch(kind, index)) in &mut swatches {
+        border.0 = swatch_border(kind.value(&config) as usize == *index, &theme);
+    }
+}
+
+fn restyle_widgets(
+    theme: Res<ActiveTheme>,
+    mut backgrounds: Query<&mut BackgroundColor, Or<(With<SliderTrack>, With<StepButton>)>>,
+) {
+    for mut background in &mut backgrounds {
+        background.0 = theme.button_hovered;
+    }
+}
+
+fn rebuild_preview(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    theme: Res<ActiveTheme>,
+    preview_boards: Query<Entity, With<PreviewBoard>>,
+) {
+    for board in &preview_boards {
+        commands
+            .entity(board)
+            .despawn_descendants()
+            .with_children(|board| spawn_preview_tiles(board, &config, &theme));
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{despawn_screen, SystemState};
+use crate::{despawn_screen, loading::GameAssets, SystemState};
 
 pub fn splash_plugin(app: &mut App) {
     // As this plugin is managing the splash screen, it will focus on the state `GameState::Splash`
@@ -23,8 +23,8 @@ struct OnSplashScreen;
 #[derive(Resource, Deref, DerefMut)]
 struct SplashTimer(Timer);
 
-fn splash_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let background = asset_server.load("squaregg-chan.png");
+fn splash_setup(mut commands: Commands, assets: Res<GameAssets>) {
+    let background = assets.squaregg_chan.clone();
     // Display the logo
     commands
         .spawn((
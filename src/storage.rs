@@ -0,0 +1,122 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{color::Color, log::warn};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+
+const APP_DIR: &str = "squaregg";
+
+// where results, shares and saves are kept between runs
+pub fn data_dir() -> PathBuf {
+    platform_dir("XDG_DATA_HOME", ".local/share")
+}
+
+// where user editable settings are kept between runs
+pub fn config_dir() -> PathBuf {
+    platform_dir("XDG_CONFIG_HOME", ".config")
+}
+
+#[cfg(target_os = "windows")]
+fn platform_dir(_xdg_var: &str, _home_fallback: &str) -> PathBuf {
+    env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+#[cfg(target_os = "macos")]
+fn platform_dir(_xdg_var: &str, _home_fallback: &str) -> PathBuf {
+    env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join("Library/Application Support"))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn platform_dir(xdg_var: &str, home_fallback: &str) -> PathBuf {
+    env::var_os(xdg_var)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(home_fallback)))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(APP_DIR)
+}
+
+// a missing or unreadable file falls back to the default rather than losing the session. one
+// that can't be parsed is moved aside first so saving the defaults doesn't overwrite it
+pub fn load_ron<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match fs::read_to_string(path) {
+        Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+            let backup = path.with_extension("ron.bak");
+            match fs::rename(path, &backup) {
+                Ok(()) => warn!(
+                    "ignoring {}: {}, kept it as {}",
+                    path.display(),
+                    err,
+                    backup.display()
+                ),
+                Err(rename_err) => warn!(
+                    "ignoring {}: {}, couldn't keep a copy: {}",
+                    path.display(),
+                    err,
+                    rename_err
+                ),
+            }
+            T::default()
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => T::default(),
+        Err(err) => {
+            warn!("couldn't read {}: {}", path.display(), err);
+            T::default()
+        }
+    }
+}
+
+pub fn save_ron<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(io::Error::other)?;
+    fs::write(path, contents)
+}
+
+// today's UTC date as yyyy-mm-dd
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() / 86_400) as i64;
+
+    // days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// colours are stored as srgba so the files stay readable, use with `#[serde(with = "srgba")]`
+pub mod srgba {
+    use bevy::color::ColorToComponents;
+
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        color.to_srgba().to_f32_array().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        <[f32; 4]>::deserialize(deserializer).map(|[r, g, b, a]| Color::srgba(r, g, b, a))
+    }
+}
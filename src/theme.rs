@@ -0,0 +1,249 @@
+use std::{collections::HashMap, fs, time::SystemTime};
+
+use bevy::{
+    asset::{
+        io::{file::FileAssetReader, Reader},
+        AssetLoader, LoadContext,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{loading::GameAssets, menu::settings::GameConfig, storage::srgba};
+
+pub fn theme_plugin(app: &mut App) {
+    app.init_asset::<Theme>()
+        .init_asset_loader::<ThemeLoader>()
+        .init_resource::<ActiveTheme>()
+        .insert_resource(ThemeWatcher {
+            timer: Timer::from_seconds(WATCH_SECS, TimerMode::Repeating),
+            modified: HashMap::new(),
+        })
+        .add_systems(Update, (watch_theme_files, apply_theme).chain());
+}
+
+// bundled themes, each one is assets/themes/<name>.theme.ron
+pub const THEMES: [&str; 4] = ["classic", "midnight", "pastel", "forest"];
+
+// how often theme files are checked for edits
+const WATCH_SECS: f32 = 1.;
+
+// every colour the board, HUD and menus draw with, missing entries fall back to the classic theme
+#[derive(Asset, TypePath, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Theme {
+    #[serde(with = "srgba")]
+    pub tile: Color,
+    #[serde(with = "srgba")]
+    pub tile_selected: Color,
+    #[serde(with = "srgba")]
+    pub selection_rectangle: Color,
+    #[serde(with = "srgba")]
+    pub prev_area: Color,
+    #[serde(with = "srgba")]
+    pub button: Color,
+    #[serde(with = "srgba")]
+    pub button_hovered: Color,
+    #[serde(with = "srgba")]
+    pub button_pressed: Color,
+    #[serde(with = "srgba")]
+    pub button_hovered_pressed: Color,
+    #[serde(with = "srgba")]
+    pub accent: Color,
+    #[serde(with = "srgba")]
+    pub text: Color,
+    #[serde(with = "srgba")]
+    pub muted_text: Color,
+    #[serde(with = "srgba")]
+    pub warning: Color,
+    #[serde(with = "srgba")]
+    pub panel: Color,
+    #[serde(with = "srgba")]
+    pub overlay: Color,
+    #[serde(with = "srgba")]
+    pub outline: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            tile: Color::hsl(0.2, 0.2, 0.9),
+            tile_selected: Color::srgb(0.20, 0.8, 0.70),
+            selection_rectangle: Color::srgba(0.9, 0.8, 0.7, 0.2),
+            prev_area: Color::srgba(0.9, 0.8, 0.7, 0.2),
+            button: Color::srgb(0.15, 0.15, 0.15),
+            button_hovered: Color::srgb(0.25, 0.25, 0.25),
+            button_pressed: Color::srgb(0.35, 0.75, 0.35),
+            button_hovered_pressed: Color::srgb(0.25, 0.65, 0.25),
+            accent: Color::srgb(0.20, 0.8, 0.70),
+            text: Color::WHITE,
+            muted_text: Color::srgb(0.5, 0.5, 0.5),
+            warning: Color::srgb(0.9, 0.25, 0.2),
+            panel: Color::srgb(0.15, 0.15, 0.15),
+            overlay: Color::srgb(0., 0.1, 0.1),
+            outline: Color::srgb(0.1, 0.1, 0.1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum ColorVision {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorVision {
+    pub const ALL: [ColorVision; 4] = [
+        ColorVision::Standard,
+        ColorVision::Deuteranopia,
+        ColorVision::Protanopia,
+        ColorVision::Tritanopia,
+    ];
+
+    pub const NAMES: [&'static str; 4] = ["Standard", "Deuteranopia", "Protanopia", "Tritanopia"];
+}
+
+impl Theme {
+    // swaps the colours that carry meaning for ones that stay distinct, palettes are from Okabe and Ito
+    pub fn with_accessibility(mut self, vision: ColorVision, high_contrast: bool) -> Self {
+        match vision {
+            ColorVision::Standard => {}
+            ColorVision::Deuteranopia | ColorVision::Protanopia => {
+                self.tile_selected = Color::srgb(0.0, 0.45, 0.70);
+                self.selection_rectangle = Color::srgba(0.0, 0.45, 0.70, 0.25);
+                self.prev_area = Color::srgba(0.94, 0.89, 0.26, 0.3);
+                self.button_pressed = Color::srgb(0.0, 0.45, 0.70);
+                self.button_hovered_pressed = Color::srgb(0.0, 0.36, 0.56);
+                self.accent = Color::srgb(0.34, 0.71, 0.91);
+                self.warning = Color::srgb(0.90, 0.62, 0.0);
+            }
+            ColorVision::Tritanopia => {
+                self.tile_selected = Color::srgb(0.84, 0.37, 0.0);
+                self.selection_rectangle = Color::srgba(0.84, 0.37, 0.0, 0.25);
+                self.prev_area = Color::srgba(0.0, 0.62, 0.45, 0.3);
+                self.button_pressed = Color::srgb(0.84, 0.37, 0.0);
+                self.button_hovered_pressed = Color::srgb(0.67, 0.3, 0.0);
+                self.accent = Color::srgb(0.8, 0.47, 0.65);
+                self.warning = Color::srgb(0.84, 0.2, 0.2);
+            }
+        }
+
+        if high_contrast {
+            self.tile = Color::WHITE;
+            if vision == ColorVision::Standard {
+                self.tile_selected = Color::srgb(1.0, 0.8, 0.0);
+            }
+            self.selection_rectangle = self.selection_rectangle.with_alpha(0.45);
+            self.prev_area = self.prev_area.with_alpha(0.5);
+            self.button = Color::BLACK;
+            self.button_hovered = Color::srgb(0.35, 0.35, 0.35);
+            self.text = Color::WHITE;
+            self.muted_text = Color::srgb(0.8, 0.8, 0.8);
+            self.panel = Color::BLACK;
+            self.overlay = Color::BLACK;
+            self.outline = Color::BLACK;
+        }
+
+        self
+    }
+}
+
+// the colours currently in use, starts as the classic theme until the chosen file has loaded
+#[derive(Resource, Deref, Default, PartialEq)]
+pub struct ActiveTheme(Theme);
+
+#[derive(Resource)]
+struct ThemeWatcher {
+    timer: Timer,
+    modified: HashMap<String, SystemTime>,
+}
+
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Theme, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+pub fn theme_path(name: &str) -> String {
+    format!("themes/{}.theme.ron", name)
+}
+
+// bevy only watches assets with the file_watcher feature, so edited theme files are picked up by polling
+fn watch_theme_files(
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut watcher: ResMut<ThemeWatcher>,
+) {
+    if !watcher.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    for name in THEMES {
+        let path = theme_path(name);
+
+        let Ok(modified) = fs::metadata(assets.join(&path)).and_then(|meta| meta.modified()) else {
+            continue;
+        };
+
+        if let Some(previous) = watcher.modified.insert(path.clone(), modified) {
+            if previous != modified {
+                asset_server.reload(path);
+            }
+        }
+    }
+}
+
+// switches to the chosen theme once it has loaded, and again whenever its file is reloaded or the
+// accessibility settings change
+fn apply_theme(
+    config: Res<GameConfig>,
+    assets: Res<GameAssets>,
+    theme_assets: Res<Assets<Theme>>,
+    mut asset_events: EventReader<AssetEvent<Theme>>,
+    mut active_theme: ResMut<ActiveTheme>,
+) {
+    let handle = assets.themes.get(&config.theme);
+
+    let reloaded = asset_events.read().any(|event| {
+        handle.is_some_and(|handle| {
+            event.is_loaded_with_dependencies(handle) || event.is_modified(handle)
+        })
+    });
+
+    if !reloaded && !config.is_changed() {
+        return;
+    }
+
+    // the classic colours stand in until the file loads, or if it can't be loaded at all
+    let theme = handle
+        .and_then(|handle| theme_assets.get(handle))
+        .cloned()
+        .unwrap_or_default();
+
+    active_theme.set_if_neq(ActiveTheme(
+        theme.with_accessibility(config.color_vision, config.high_contrast),
+    ));
+}